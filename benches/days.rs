@@ -0,0 +1,38 @@
+//! Benchmarks of every day's solutions (all of their variants) on their real inputs. Days whose
+//! input is missing are skipped.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::fs;
+use std::hint::black_box;
+
+fn days(c: &mut Criterion) {
+    for year in aoc::days::YEARS {
+        let mut group = c.benchmark_group(year.number.to_string());
+        // Some days take a good part of a second, so fewer samples keep the whole suite quick.
+        group.sample_size(20);
+        for day in year.days {
+            let path = format!(
+                "{}/inputs/{}/{:02}.txt",
+                env!("CARGO_MANIFEST_DIR"),
+                year.number,
+                day.number
+            );
+            let Ok(input) = fs::read_to_string(&path) else {
+                eprintln!("Skipping day {}: no input at {path}", day.number);
+                continue;
+            };
+            let params = aoc::Params::default();
+            for &(variant, solver) in day.variants {
+                let id = match variant {
+                    "default" => format!("day {:02}", day.number),
+                    _ => format!("day {:02} {variant}", day.number),
+                };
+                group.bench_function(id, |b| b.iter(|| solver(black_box(&input), &params)));
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
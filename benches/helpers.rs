@@ -0,0 +1,70 @@
+//! Micro-benchmarks of the hot shared helpers and of some of the choices made on the days, on the
+//! real inputs. Benchmarks whose input is missing are skipped.
+
+use aoc::days::y2024::day_11_plutonian_pebbles::count_stones;
+use aoc::days::y2024::day_22_monkey_market::{rand, simulate_buyers};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::fs;
+use std::hint::black_box;
+
+// The input of a day, or None if it's missing, for skipping the benchmarks that use it.
+fn read_input(day_num: usize, benchmark: &str) -> Option<String> {
+    let path = format!(
+        "{}/inputs/2024/{day_num:02}.txt",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let input = fs::read_to_string(&path).ok();
+    if input.is_none() {
+        eprintln!("Skipping {benchmark}: no input at {path}");
+    }
+    input
+}
+
+fn parsing(c: &mut Criterion) {
+    if let Some(numbers) = read_input(1, "parse_numbers") {
+        c.bench_function("parse_numbers", |b| {
+            b.iter(|| aoc::parse_numbers::<u32>(black_box(&numbers)))
+        });
+    }
+    if let Some(grid) = read_input(16, "parse_grid") {
+        c.bench_function("parse_grid", |b| {
+            b.iter(|| aoc::parse_grid(black_box(&grid), Ok))
+        });
+    }
+}
+
+fn day_helpers(c: &mut Criterion) {
+    if let Some(input) = read_input(11, "count_stones") {
+        let stones: Vec<u64> = aoc::parse_numbers(&input).unwrap();
+        c.bench_function("count_stones 75", |b| {
+            b.iter(|| count_stones(black_box(&stones), 75))
+        });
+    }
+    c.bench_function("rand 2000", |b| {
+        b.iter(|| (0..2000).fold(black_box(123), |s, _| rand(s)))
+    });
+}
+
+// Day 22 keeps the last 4 price diffs packed on a u32, because a [u8; 4] turned out to be slower.
+fn day_22_diffs(c: &mut Criterion) {
+    let Some(input) = read_input(22, "day 22 diffs") else {
+        return;
+    };
+    let secrets: Vec<u32> = aoc::parse_numbers(&input).unwrap();
+    let mut group = c.benchmark_group("day 22 diffs");
+    group.sample_size(20);
+    group.bench_function("u32", |b| {
+        b.iter(|| simulate_buyers(black_box(&secrets), 0_u32, |diffs, diff| diffs << 8 | diff))
+    });
+    group.bench_function("[u8; 4]", |b| {
+        b.iter(|| {
+            simulate_buyers(black_box(&secrets), [0_u8; 4], |[_, a, b, c], diff| {
+                [a, b, c, diff as u8]
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parsing, day_helpers, day_22_diffs);
+criterion_main!(benches);
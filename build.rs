@@ -0,0 +1,49 @@
+// With the embed-inputs feature, bakes the puzzle inputs on inputs/YEAR/NN.txt into the executable,
+// as the `INPUTS` list of (year, day, input) on $OUT_DIR/embedded_inputs.rs. Without it, or for days
+// whose input is missing or empty, the list is just empty.
+
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    let mut inputs = vec![];
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let inputs_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("inputs");
+        println!("cargo::rerun-if-changed={}", inputs_dir.display());
+        for year_dir in fs::read_dir(&inputs_dir).into_iter().flatten().flatten() {
+            let Some(year) = parse_name(&year_dir.path(), "") else {
+                continue;
+            };
+            for file in fs::read_dir(year_dir.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                let path = file.path();
+                let is_empty = file.metadata().map_or(true, |m| m.len() == 0);
+                if let Some(day) = parse_name(&path, "txt")
+                    && !is_empty
+                {
+                    inputs.push((year, day, path));
+                }
+            }
+        }
+    }
+    inputs.sort();
+
+    let entries: String = inputs
+        .iter()
+        .map(|(year, day, path)| format!("    ({year}, {day}, include_str!({path:?})),\n"))
+        .collect();
+    let code = format!("pub const INPUTS: &[(usize, usize, &str)] = &[\n{entries}];\n");
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out_path, code).unwrap();
+}
+
+// The number that a file is named after, like 2024 for the inputs/2024 directory or 1 for 01.txt.
+fn parse_name(path: &Path, extension: &str) -> Option<usize> {
+    let has_extension = path.extension().unwrap_or_default() == extension;
+    let number = path.file_stem()?.to_str()?.parse().ok()?;
+    has_extension.then_some(number)
+}
@@ -0,0 +1,158 @@
+use crate::runner::{self, Job};
+use anyhow::bail;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_RUNS: usize = 10;
+
+// The README goal: the whole set of puzzles should run in under a second.
+const DEFAULT_TOTAL_BUDGET_MS: u64 = 1000;
+
+pub struct Budgets {
+    all_days: Option<Duration>,
+    by_day: HashMap<usize, Duration>,
+    total: Duration,
+}
+
+impl Budgets {
+    // Day budgets without a day number apply to all days that don't have a specific one.
+    pub fn new(day_budgets: &[(Option<usize>, u64)], total_budget: Option<u64>) -> Budgets {
+        let mut budgets = Budgets {
+            all_days: None,
+            by_day: HashMap::new(),
+            total: Duration::from_millis(total_budget.unwrap_or(DEFAULT_TOTAL_BUDGET_MS)),
+        };
+        for &(day_num, ms) in day_budgets {
+            let budget = Duration::from_millis(ms);
+            match day_num {
+                Some(n) => _ = budgets.by_day.insert(n, budget),
+                None => budgets.all_days = Some(budget),
+            }
+        }
+        budgets
+    }
+
+    fn for_day(&self, day_num: usize) -> Option<Duration> {
+        self.by_day.get(&day_num).copied().or(self.all_days)
+    }
+}
+
+// Runs each day `runs` times, one after the other so that timings don't interfere with each other,
+// and prints timing statistics for each one. Budgets are checked against the median times. Days that
+// fail are reported as such, and the rest of them still run.
+pub fn run_benchmarks(jobs: &[Job], runs: usize, budgets: &Budgets) -> aoc::Result<()> {
+    println!(
+        "{:<7} {:>10} {:>10} {:>10} {:>10}",
+        "", "min", "median", "p95", "max"
+    );
+    let mut total = Duration::ZERO;
+    let mut over_budget_count = 0;
+    let mut fail_count = 0;
+    for job in jobs {
+        let day_num = job.day_num;
+        let result = runner::run_on_day_thread(|| {
+            let input = job.read_input()?;
+            let mut times = Vec::with_capacity(runs);
+            for _ in 0..runs {
+                let instant = Instant::now();
+                let answer = (job.solver)(&input, &job.params);
+                times.push(instant.elapsed());
+                answer.map_err(|err| aoc::locate_error(err, &input, job.input_name()))?;
+            }
+            Ok(times)
+        });
+        let times = match result {
+            Ok(times) => times,
+            Err(err) => {
+                fail_count += 1;
+                println!("{:<7} FAILED ({err:#})", format!("Day {day_num}"));
+                continue;
+            }
+        };
+        let stats = Stats::new(times);
+        total += stats.median;
+
+        let mut budget_note = String::new();
+        if let Some(budget) = budgets.for_day(day_num)
+            && stats.median > budget
+        {
+            budget_note = format!("  OVER BUDGET ({})", format_duration(budget));
+            over_budget_count += 1;
+        }
+        println!(
+            "{:<7} {:>10} {:>10} {:>10} {:>10}{budget_note}",
+            format!("Day {day_num}"),
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.p95),
+            format_duration(stats.max),
+        );
+    }
+
+    let mut budget_note = String::new();
+    if total > budgets.total {
+        budget_note = format!("  OVER BUDGET ({})", format_duration(budgets.total));
+        over_budget_count += 1;
+    }
+    println!(
+        "Total (sum of medians, {runs} runs): {}{budget_note}",
+        format_duration(total)
+    );
+
+    match (fail_count, over_budget_count) {
+        (0, 0) => Ok(()),
+        (0, _) => bail!("{over_budget_count} time budgets exceeded"),
+        (_, 0) => bail!("{fail_count} of {} days failed", jobs.len()),
+        _ => bail!(
+            "{fail_count} of {} days failed, and {over_budget_count} time budgets exceeded",
+            jobs.len()
+        ),
+    }
+}
+
+struct Stats {
+    min: Duration,
+    median: Duration,
+    p95: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn new(mut times: Vec<Duration>) -> Stats {
+        times.sort();
+        let n = times.len();
+        let median = if n.is_multiple_of(2) {
+            (times[n / 2 - 1] + times[n / 2]) / 2
+        } else {
+            times[n / 2]
+        };
+        // Nearest-rank percentile.
+        let p95 = times[(n * 95).div_ceil(100) - 1];
+        Stats {
+            min: times[0],
+            median,
+            p95,
+            max: times[n - 1],
+        }
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+#[test]
+fn stats_test() {
+    let ms = |n| Duration::from_millis(n);
+    let stats = Stats::new((1..=20).rev().map(ms).collect());
+    assert_eq!(stats.min, ms(1));
+    assert_eq!(stats.median, Duration::from_micros(10500));
+    assert_eq!(stats.p95, ms(19));
+    assert_eq!(stats.max, ms(20));
+
+    let stats = Stats::new(vec![ms(7)]);
+    assert_eq!(
+        (stats.min, stats.median, stats.p95, stats.max),
+        (ms(7), ms(7), ms(7), ms(7))
+    );
+}
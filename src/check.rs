@@ -0,0 +1,156 @@
+use crate::runner::{DayResult, Job};
+use anyhow::{Context, bail};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::{fs, iter};
+
+// Compares each part of the given day answers (or only the given part) with the expected ones in the
+// year's answers file and prints a PASS/FAIL line for each of them. Fails if any of them doesn't
+// match. If `update` is set, the answers file gets rewritten with the given answers instead.
+pub fn check_answers(
+    year: usize,
+    answers: &[(usize, aoc::Answer)],
+    part: Option<usize>,
+    update: bool,
+) -> aoc::Result<()> {
+    let answers_file = format!("answers/{year}.txt");
+    let mut expected_answers = read_answers_file(&answers_file, update)?;
+    let mut fail_count = 0;
+    for (day_num, answer) in answers {
+        let day = format!("Day {day_num}");
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(err) => {
+                println!("{day:<7} {:<6}  FAIL  {err:#}", "");
+                fail_count += 1;
+                continue;
+            }
+        };
+        let expected = expected_answers.get(day_num).map_or("", String::as_str);
+        for (part_num, expected_part, actual_part) in compare_parts(expected, answer, part) {
+            let part = format!("part {part_num}");
+            match (expected_part, actual_part) {
+                (Some(e), Some(a)) if e == a => println!("{day:<7} {part}  PASS  {a}"),
+                (e, a) => {
+                    let (e, a) = (e.unwrap_or("nothing"), a.unwrap_or("nothing"));
+                    println!("{day:<7} {part}  FAIL  expected {e}, got {a}");
+                    fail_count += 1;
+                }
+            }
+        }
+    }
+
+    if update {
+        for (day_num, answer) in answers {
+            let Ok(answer) = answer else {
+                bail!("cannot update {answers_file}: day {day_num} failed");
+            };
+            expected_answers.insert(*day_num, answer.to_string());
+        }
+        let contents = expected_answers
+            .iter()
+            .map(|(day_num, answer)| format!("Day {day_num}: {answer}\n"))
+            .join("");
+        fs::create_dir_all("answers")
+            .and_then(|_| fs::write(&answers_file, contents))
+            .with_context(|| format!("error writing {answers_file}"))?;
+        println!("Updated {answers_file}");
+    } else if fail_count > 0 {
+        bail!("answers don't match {answers_file} ({fail_count} failures)");
+    }
+    Ok(())
+}
+
+// Reads the expected answers by day number. A missing file is only OK when it's about to be created.
+fn read_answers_file(path: &str, update: bool) -> aoc::Result<BTreeMap<usize, String>> {
+    let contents = match fs::read_to_string(path) {
+        Err(err) if update && err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        res => res.with_context(|| format!("error reading {path}"))?,
+    };
+    contents
+        .lines()
+        .map(|line| {
+            let (day, answer) = line
+                .strip_prefix("Day ")
+                .and_then(|l| l.split_once(": "))
+                .with_context(|| format!("invalid line in {path}: '{line}'"))?;
+            Ok((day.parse()?, answer.to_string()))
+        })
+        .collect()
+}
+
+// Checks that all the variants of each day give the same answers, printing an OK/MISMATCH line for
+// each day. The results must be in the same order as the jobs, which are grouped by day.
+pub fn cross_check_variants(
+    jobs: &[Job],
+    results: impl Iterator<Item = DayResult>,
+) -> aoc::Result<()> {
+    let mut mismatch_count = 0;
+    let results = jobs.iter().zip(results);
+    for (day_num, day_results) in &results.chunk_by(|(job, _)| job.day_num) {
+        let day_results = day_results
+            .map(|(job, result)| {
+                let answer = match result {
+                    Ok((answers, _)) => answers.to_string(),
+                    Err(err) => format!("FAILED ({err:#})"),
+                };
+                (job.variant, answer)
+            })
+            .collect_vec();
+        let all_ok = day_results
+            .iter()
+            .all(|(_, answer)| !answer.starts_with("FAILED"));
+        let day = format!("Day {day_num}");
+        if all_ok && day_results.iter().map(|(_, answer)| answer).all_equal() {
+            let variants = day_results.iter().map(|(variant, _)| variant).join(", ");
+            println!("{day:<7} OK        {} ({variants})", day_results[0].1);
+        } else {
+            println!("{day:<7} MISMATCH");
+            for (variant, answer) in day_results {
+                println!("  {variant}: {answer}");
+            }
+            mismatch_count += 1;
+        }
+    }
+    if mismatch_count > 0 {
+        bail!("variants disagree on {mismatch_count} days");
+    }
+    Ok(())
+}
+
+// Pairs each part of an expected answers line with the actual answer for that part, skipping the
+// parts not selected by `part` and the ones that neither of them have.
+pub fn compare_parts<'a>(
+    expected: &'a str,
+    answers: &'a aoc::Answers,
+    part: Option<usize>,
+) -> impl Iterator<Item = (usize, Option<&'a str>, Option<&'a str>)> {
+    let actual = [Some(answers.part1.as_str()), answers.part2.as_deref()];
+    let parts = iter::zip(split_parts(expected), actual);
+    (1..).zip(parts).filter_map(move |(part_num, (e, a))| {
+        let selected = part.is_none_or(|p| p == part_num);
+        (selected && (e.is_some() || a.is_some())).then_some((part_num, e, a))
+    })
+}
+
+// Splits an answers file line into its part 1 and part 2 components. Day 25 has a single part. Part 1
+// answers never contain spaces, so anything after the first space is the part 2 answer.
+fn split_parts(answer: &str) -> [Option<&str>; 2] {
+    match answer.split_once(' ') {
+        Some((p1, p2)) => [Some(p1), Some(p2)],
+        None if answer.is_empty() => [None, None],
+        None => [Some(answer), None],
+    }
+}
+
+#[test]
+fn split_parts_test() {
+    assert_eq!(split_parts("1 2"), [Some("1"), Some("2")]);
+    assert_eq!(split_parts("2815"), [Some("2815"), None]);
+    assert_eq!(
+        split_parts("1366 bs,cf,cn"),
+        [Some("1366"), Some("bs,cf,cn")]
+    );
+    assert_eq!(split_parts(""), [None, None]);
+}
@@ -0,0 +1,167 @@
+use crate::output::{self, Format};
+use crate::runner::{self, DayResult, DayUpdate, Job};
+use anyhow::anyhow;
+use itertools::Itertools;
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How often the run times of running days are refreshed.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+// Answers and error messages are cut to this many characters so that lines don't wrap, which would
+// throw off the redrawing of the table.
+const MAX_TEXT_LEN: usize = 60;
+
+enum Status {
+    Queued,
+    Running(Instant),
+    Finished(DayResult),
+}
+
+// Whether the dashboard can be shown. It needs a terminal to be redrawn on, so it's only shown with
+// the text format when stdout is a TTY. Otherwise, results are printed as usual.
+pub fn is_available(format: Format) -> bool {
+    matches!(format, Format::Text) && io::stdout().is_terminal()
+}
+
+// Runs the day jobs like `runner::run_days()`, while showing a table with the status of all of them,
+// which is updated as they start and finish. Returns their results in the same order as the jobs.
+pub fn run_dashboard(
+    jobs: &[Job],
+    workers: usize,
+    timeout: Option<Duration>,
+    part: Option<usize>,
+) -> Vec<DayResult> {
+    // Updates are forwarded from another thread, so that the table can be refreshed while waiting
+    // for them.
+    let (sender, receiver) = mpsc::channel();
+    let updates = runner::watch_days(jobs, workers, timeout);
+    thread::spawn(move || {
+        for update in updates {
+            if sender.send(update).is_err() {
+                break;
+            }
+        }
+    });
+
+    let start = Instant::now();
+    let mut statuses = jobs.iter().map(|_| Status::Queued).collect_vec();
+    let mut drawn_lines = 0;
+    loop {
+        let finished = match receiver.recv_timeout(REFRESH_INTERVAL) {
+            Ok((i, DayUpdate::Started(day_start))) => {
+                statuses[i] = Status::Running(day_start);
+                false
+            }
+            Ok((i, DayUpdate::Finished(result))) => {
+                statuses[i] = Status::Finished(result);
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+        let lines = table_lines(jobs, &statuses, part, start.elapsed());
+        draw(&lines, drawn_lines);
+        drawn_lines = lines.len();
+        if finished {
+            break;
+        }
+    }
+
+    let results = statuses.into_iter().map(|status| match status {
+        Status::Finished(result) => result,
+        _ => Err(anyhow!("worker thread disconnected")),
+    });
+    results.collect()
+}
+
+// Prints the table lines over the previously drawn ones.
+fn draw(lines: &[String], drawn_lines: usize) {
+    let mut frame = String::new();
+    if drawn_lines > 0 {
+        // Move the cursor up to the first line of the previous table.
+        frame += &format!("\x1b[{drawn_lines}A");
+    }
+    for line in lines {
+        // Clear the previous contents of the line before printing the new ones.
+        frame += &format!("\r\x1b[2K{line}\n");
+    }
+    let mut stdout = io::stdout().lock();
+    _ = stdout.write_all(frame.as_bytes());
+    _ = stdout.flush();
+}
+
+fn table_lines(
+    jobs: &[Job],
+    statuses: &[Status],
+    part: Option<usize>,
+    elapsed: Duration,
+) -> Vec<String> {
+    let mut lines = jobs
+        .iter()
+        .zip(statuses)
+        .map(|(job, status)| status_line(job.day_num, status, part))
+        .collect_vec();
+    let finished = statuses.iter().filter(|s| matches!(s, Status::Finished(_)));
+    let failed = statuses
+        .iter()
+        .filter(|s| matches!(s, Status::Finished(Err(_))));
+    lines.push(format!(
+        "{}/{} done, {} failed ({elapsed:.1?})",
+        finished.count(),
+        jobs.len(),
+        failed.count()
+    ));
+    lines
+}
+
+fn status_line(day_num: usize, status: &Status, part: Option<usize>) -> String {
+    let (status, time, text) = match status {
+        Status::Queued => ("queued", String::new(), String::new()),
+        Status::Running(start) => ("running", format!("{:.2?}", start.elapsed()), String::new()),
+        Status::Finished(Ok((answers, elapsed))) => (
+            "done",
+            format!("{elapsed:.2?}"),
+            output::format_answer(answers, part),
+        ),
+        Status::Finished(Err(err)) => {
+            // Only the first line of the error fits; input errors have a snippet on the next ones.
+            let message = format!("{err:#}");
+            let message = message.lines().next().unwrap_or_default().to_string();
+            ("FAILED", String::new(), message)
+        }
+    };
+    let line = format!(
+        "{:<7} {status:<7} {time:>10}  {}",
+        format!("Day {day_num}"),
+        truncate(&text, MAX_TEXT_LEN)
+    );
+    line.trim_end().to_string()
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        return s.to_string();
+    }
+    let mut truncated: String = s.chars().take(max_len - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[test]
+fn status_line_test() {
+    assert_eq!(status_line(3, &Status::Queued, None), "Day 3   queued");
+    let result = Ok((aoc::answers(161, 48).unwrap(), Duration::from_micros(1234)));
+    assert_eq!(
+        status_line(3, &Status::Finished(result), Some(2)),
+        "Day 3   done        1.23ms  48"
+    );
+    let result = Err(anyhow!("in.txt:1:2: invalid number\n1 | x\n  |  ^"));
+    assert_eq!(
+        status_line(17, &Status::Finished(result), None),
+        "Day 17  FAILED              in.txt:1:2: invalid number"
+    );
+    assert_eq!(truncate("abcdef", 4), "abc…");
+}
@@ -0,0 +1,128 @@
+//! The solutions of all years and days, and a registry for finding them by number or title.
+
+use crate::{Answer, Params, Result};
+use anyhow::Context;
+use itertools::Itertools;
+
+/// A day's solution, which gets the puzzle input and parameters for running it on other inputs.
+pub type Solver = fn(&str, &Params) -> Answer;
+
+pub struct Year {
+    pub number: usize,
+    pub days: &'static [Day],
+}
+
+pub struct Day {
+    pub number: usize,
+    pub title: &'static str,
+    /// Named solutions for the day. The first one is the default.
+    pub variants: &'static [(&'static str, Solver)],
+}
+
+// Builds the `DAYS` registry of a year. Each day has a default solution in its main module, and
+// optionally some alternative solutions (variants) in other modules. Days marked with `(params)`
+// take parameters, and have a `run_with_params()` function instead of `run()`. The modules are
+// declared with plain `pub mod` items next to the registry, so that rustfmt can find them.
+macro_rules! days {
+    (@solver $module:ident) => {
+        |input, params| {
+            anyhow::ensure!(params.is_empty(), "this day doesn't take parameters");
+            $module::run(input)
+        }
+    };
+    (@solver $module:ident params) => {
+        $module::run_with_params
+    };
+    ($($num:literal $title:literal: $module:ident $(($params:ident))?
+        $(, $variant:ident = $variant_module:ident)*;)*) => {
+        /// All the days, in order.
+        pub const DAYS: &[$crate::days::Day] = &[$($crate::days::Day {
+            number: $num,
+            title: $title,
+            variants: &[
+                ("default", days!(@solver $module $($params)?))
+                $(, (stringify!($variant), days!(@solver $variant_module)))*
+            ],
+        }),*];
+    };
+}
+
+// Builds the `YEARS` registry from the year modules. Each year module has a `days!` registry of its
+// own, with its day modules on a directory of the same name.
+macro_rules! years {
+    ($($year:literal: $module:ident;)*) => {
+        /// All the years, in order.
+        pub const YEARS: &[Year] = &[$(Year { number: $year, days: $module::DAYS }),*];
+    };
+}
+
+pub mod y2024;
+
+years! {
+    2024: y2024;
+}
+
+/// Finds a year by its number.
+pub fn find_year(year: usize) -> Option<&'static Year> {
+    YEARS.iter().find(|y| y.number == year)
+}
+
+/// The latest year, which is the one used when none is given.
+pub fn latest_year() -> &'static Year {
+    YEARS.last().expect("no years registered")
+}
+
+impl Year {
+    /// Finds a day by its number.
+    pub fn find(&self, day_num: usize) -> Option<&'static Day> {
+        self.days.iter().find(|day| day.number == day_num)
+    }
+
+    /// Finds a day by its puzzle title, ignoring case, e.g. "Crossed Wires".
+    pub fn find_by_title(&self, title: &str) -> Option<&'static Day> {
+        self.days
+            .iter()
+            .find(|day| day.title.eq_ignore_ascii_case(title))
+    }
+}
+
+impl Day {
+    /// Returns the variant with the given name, or the default one.
+    pub fn variant(&self, name: Option<&str>) -> Result<(&'static str, Solver)> {
+        let Some(name) = name else {
+            return Ok(self.variants[0]);
+        };
+        let variant = self.variants.iter().find(|(n, _)| *n == name);
+        variant.copied().with_context(|| {
+            let variants = self.variants.iter().map(|(n, _)| n).join(", ");
+            format!(
+                "day {} has no '{name}' variant (available: {variants})",
+                self.number
+            )
+        })
+    }
+
+    /// Runs the default solution on the given puzzle input.
+    pub fn run(&self, input: &str) -> Answer {
+        (self.variants[0].1)(input, &Params::default())
+    }
+}
+
+#[test]
+fn find_test() {
+    let year = find_year(2024).unwrap();
+    assert_eq!(year.find(24).unwrap().title, "Crossed Wires");
+    assert_eq!(year.find_by_title("crossed wires").unwrap().number, 24);
+    assert!(year.find(26).is_none());
+    assert!(year.find_by_title("Crossed").is_none());
+    assert!(find_year(2015).is_none());
+    assert_eq!(latest_year().number, 2024);
+
+    let day = year.find(16).unwrap();
+    assert_eq!(day.variant(None).unwrap().0, "default");
+    assert_eq!(
+        day.variant(Some("custom_dijkstra")).unwrap().0,
+        "custom_dijkstra"
+    );
+    assert!(day.variant(Some("nope")).is_err());
+}
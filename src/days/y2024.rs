@@ -0,0 +1,56 @@
+//! The solutions of Advent of Code 2024.
+
+pub mod day_01_historian_hysteria;
+pub mod day_02_red_nosed_reports;
+pub mod day_03_mull_it_over;
+pub mod day_04_ceres_search;
+pub mod day_05_print_queue;
+pub mod day_06_guard_gallivant;
+pub mod day_07_bridge_repair;
+pub mod day_08_resonant_collinearity;
+pub mod day_09_disk_fragmenter;
+pub mod day_10_hoof_it;
+pub mod day_11_plutonian_pebbles;
+pub mod day_12_garden_groups;
+pub mod day_13_claw_contraption;
+pub mod day_14_restroom_redoubt;
+pub mod day_15_warehouse_woes;
+pub mod day_16_reindeer_maze;
+pub mod day_16_reindeer_maze_custom_dijkstra;
+pub mod day_17_chronospatial_computer;
+pub mod day_18_ram_run;
+pub mod day_19_linen_layout;
+pub mod day_20_race_condition;
+pub mod day_21_keypad_conundrum;
+pub mod day_22_monkey_market;
+pub mod day_23_lan_party;
+pub mod day_24_crossed_wires;
+pub mod day_25_code_chronicle;
+
+days! {
+    1 "Historian Hysteria": day_01_historian_hysteria;
+    2 "Red-Nosed Reports": day_02_red_nosed_reports;
+    3 "Mull It Over": day_03_mull_it_over;
+    4 "Ceres Search": day_04_ceres_search;
+    5 "Print Queue": day_05_print_queue;
+    6 "Guard Gallivant": day_06_guard_gallivant;
+    7 "Bridge Repair": day_07_bridge_repair;
+    8 "Resonant Collinearity": day_08_resonant_collinearity;
+    9 "Disk Fragmenter": day_09_disk_fragmenter;
+    10 "Hoof It": day_10_hoof_it;
+    11 "Plutonian Pebbles": day_11_plutonian_pebbles;
+    12 "Garden Groups": day_12_garden_groups;
+    13 "Claw Contraption": day_13_claw_contraption;
+    14 "Restroom Redoubt": day_14_restroom_redoubt(params);
+    15 "Warehouse Woes": day_15_warehouse_woes;
+    16 "Reindeer Maze": day_16_reindeer_maze, custom_dijkstra = day_16_reindeer_maze_custom_dijkstra;
+    17 "Chronospatial Computer": day_17_chronospatial_computer;
+    18 "RAM Run": day_18_ram_run(params);
+    19 "Linen Layout": day_19_linen_layout;
+    20 "Race Condition": day_20_race_condition(params);
+    21 "Keypad Conundrum": day_21_keypad_conundrum;
+    22 "Monkey Market": day_22_monkey_market;
+    23 "LAN Party": day_23_lan_party;
+    24 "Crossed Wires": day_24_crossed_wires;
+    25 "Code Chronicle": day_25_code_chronicle;
+}
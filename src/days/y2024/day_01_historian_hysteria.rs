@@ -1,3 +1,4 @@
+use aoc::InputContext;
 use itertools::Itertools;
 
 pub fn run(input: &str) -> aoc::Answer {
@@ -22,7 +23,9 @@ pub fn run(input: &str) -> aoc::Answer {
 
 fn parse_line(line: &str) -> aoc::Result<(i64, i64)> {
     let numbers = aoc::parse_numbers(line)?;
-    let [l, r] = numbers[..].try_into()?;
+    let [l, r] = numbers[..]
+        .try_into()
+        .context_at(line, "expected two numbers")?;
     Ok((l, r))
 }
 
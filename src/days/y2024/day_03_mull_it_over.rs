@@ -1,10 +1,11 @@
+use aoc::InputContext;
 use regex::Regex;
 
 pub fn run(input: &str) -> aoc::Answer {
     aoc::answers(run_program(input, false)?, run_program(input, true)?)
 }
 
-fn run_program(input: &str, conditionals: bool) -> aoc::Result<u64> {
+pub fn run_program(input: &str, conditionals: bool) -> aoc::Result<u64> {
     let re = Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").unwrap();
     let mut sum = 0;
     let mut mul_enabled = true;
@@ -14,8 +15,9 @@ fn run_program(input: &str, conditionals: bool) -> aoc::Result<u64> {
             "don't()" => mul_enabled = false,
             _ => {
                 if !conditionals || mul_enabled {
-                    let op1 = capture.get(1).unwrap().as_str().parse::<u64>()?;
-                    let op2 = capture.get(2).unwrap().as_str().parse::<u64>()?;
+                    let [op1, op2] = [1, 2].map(|i| capture.get(i).unwrap().as_str());
+                    let op1 = op1.parse::<u64>().context_at(op1, "invalid number")?;
+                    let op2 = op2.parse::<u64>().context_at(op2, "invalid number")?;
                     sum += op1 * op2;
                 }
             }
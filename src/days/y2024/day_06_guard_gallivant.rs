@@ -9,16 +9,20 @@ pub fn run(input: &str) -> aoc::Answer {
         .find(|&(x, y)| map[y][x] == '^')
         .context("guard not found")?;
 
-    let guard_path = guard_walk(&map, start_pos).context("guard should exit the map on part 1")?;
+    let guard_path = aoc::span("part 1", || guard_walk(&map, start_pos));
+    let guard_path = guard_path.context("guard should exit the map on part 1")?;
     let guard_positions: HashSet<_> = guard_path.into_iter().collect();
 
-    let obstacles = guard_positions.iter().filter(|&&(obstacle_x, obstacle_y)| {
-        let mut map = map.clone();
-        map[obstacle_y][obstacle_x] = '#';
-        guard_walk(&map, start_pos).is_none()
+    let obstacle_count = aoc::span("part 2", || {
+        let obstacles = guard_positions.iter().filter(|&&(obstacle_x, obstacle_y)| {
+            let mut map = map.clone();
+            map[obstacle_y][obstacle_x] = '#';
+            guard_walk(&map, start_pos).is_none()
+        });
+        obstacles.count()
     });
 
-    aoc::answers(guard_positions.len(), obstacles.count())
+    aoc::answers(guard_positions.len(), obstacle_count)
 }
 
 fn guard_walk(map: &[Vec<char>], start_pos: (usize, usize)) -> Option<Vec<(usize, usize)>> {
@@ -1,4 +1,3 @@
-use anyhow::ensure;
 use itertools::Itertools;
 
 pub fn run(input: &str) -> aoc::Answer {
@@ -6,7 +5,9 @@ pub fn run(input: &str) -> aoc::Answer {
         .lines()
         .map(|line| {
             let numbers = aoc::parse_numbers(line)?;
-            ensure!(numbers.len() >= 2, "invalid calibration equation: {line}");
+            if numbers.len() < 2 {
+                return Err(aoc::input_error(line, "invalid calibration equation"));
+            }
             Ok((numbers[0], numbers[1..].to_vec()))
         })
         .try_collect()?;
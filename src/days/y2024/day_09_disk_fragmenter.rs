@@ -1,16 +1,19 @@
-use anyhow::Context;
+use aoc::InputContext;
 use itertools::Itertools;
 use std::iter::repeat_n;
 
 pub fn run(input: &str) -> aoc::Answer {
     let mut blocks = vec![];
-    for (ch, i) in input.trim().chars().zip(0..) {
-        let size = ch.to_digit(10).context("expected a digit")?;
+    let disk_map = input.trim();
+    for ((pos, ch), i) in disk_map.char_indices().zip(0..) {
+        let size = ch
+            .to_digit(10)
+            .context_at(&disk_map[pos..pos + ch.len_utf8()], "expected a digit")?;
         let block = if i % 2 == 0 { i / 2 } else { FREE };
         blocks.extend(repeat_n(block, size as usize));
     }
-    let p1_checksum = compact_blocks(blocks.clone());
-    let p2_checksum = compact_whole_files(blocks);
+    let p1_checksum = aoc::span("part 1", || compact_blocks(blocks.clone()));
+    let p2_checksum = aoc::span("part 2", || compact_whole_files(blocks));
     aoc::answers(p1_checksum, p2_checksum)
 }
 
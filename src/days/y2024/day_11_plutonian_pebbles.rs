@@ -2,10 +2,13 @@ use rustc_hash::FxHashMap as HashMap;
 
 pub fn run(input: &str) -> aoc::Answer {
     let stones = aoc::parse_numbers(input)?;
-    aoc::answers(count_stones(&stones, 25), count_stones(&stones, 75))
+    aoc::answers(
+        aoc::span("part 1", || count_stones(&stones, 25)),
+        aoc::span("part 2", || count_stones(&stones, 75)),
+    )
 }
 
-fn count_stones(stones: &[u64], blink_count: u64) -> u64 {
+pub fn count_stones(stones: &[u64], blink_count: u64) -> u64 {
     let mut counts: HashMap<u64, u64> = stones.iter().map(|&s| (s, 1)).collect();
     for _ in 0..blink_count {
         let mut new_counts = HashMap::default();
@@ -4,18 +4,25 @@ use rustc_hash::FxHashSet as HashSet;
 
 pub fn run(input: &str) -> aoc::Answer {
     let (garden, w, h) = aoc::parse_char_grid(input)?;
-    let mut regions = Vec::new();
-    let mut visited = Region::default();
-    for point in iproduct!(0..w, 0..h) {
-        if !visited.contains(&point) {
-            let region = get_region_at(point, &garden);
-            visited.extend(&region);
-            regions.push(region);
+    let regions = aoc::span("regions", || {
+        let mut regions = Vec::new();
+        let mut visited = Region::default();
+        for point in iproduct!(0..w, 0..h) {
+            if !visited.contains(&point) {
+                let region = get_region_at(point, &garden);
+                visited.extend(&region);
+                regions.push(region);
+            }
         }
-    }
+        regions
+    });
     aoc::answers(
-        regions.iter().map(get_fence_price).sum::<usize>(),
-        regions.iter().map(get_fence_bulk_price).sum::<usize>(),
+        aoc::span("part 1", || {
+            regions.iter().map(get_fence_price).sum::<usize>()
+        }),
+        aoc::span("part 2", || {
+            regions.iter().map(get_fence_bulk_price).sum::<usize>()
+        }),
     )
 }
 
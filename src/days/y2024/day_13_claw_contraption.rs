@@ -1,7 +1,8 @@
+use aoc::InputContext;
 use itertools::Itertools;
 
 pub fn run(input: &str) -> aoc::Answer {
-    let claw_machines: Vec<_> = input.split("\n\n").map(parse_claw_machine).try_collect()?;
+    let claw_machines: Vec<_> = aoc::sections(input).map(parse_claw_machine).try_collect()?;
     let min_tokens_p2 =
         |&(a, b, (px, py))| min_tokens(&(a, b, (px + 10000000000000, py + 10000000000000)));
     aoc::answers(
@@ -10,15 +11,16 @@ pub fn run(input: &str) -> aoc::Answer {
     )
 }
 
-type Point = (i64, i64);
+pub type Point = (i64, i64);
 
-fn parse_claw_machine(s: &str) -> aoc::Result<(Point, Point, Point)> {
+pub fn parse_claw_machine(s: &str) -> aoc::Result<(Point, Point, Point)> {
     let numbers = aoc::parse_numbers(s)?;
-    let [ax, ay, bx, by, price_x, price_y] = numbers[..].try_into()?;
+    let [ax, ay, bx, by, price_x, price_y] =
+        numbers[..].try_into().context_at(s, "expected 6 numbers")?;
     Ok(((ax, ay), (bx, by), (price_x, price_y)))
 }
 
-fn min_tokens(claw_machine: &(Point, Point, Point)) -> u64 {
+pub fn min_tokens(claw_machine: &(Point, Point, Point)) -> u64 {
     let &((ax, ay), (bx, by), (px, py)) = claw_machine;
     // We want to find the number of button presses, `a` and `b`, solving these equations:
     // a*ax + b*bx = px
@@ -0,0 +1,123 @@
+use anyhow::{bail, ensure};
+use aoc::InputContext;
+use itertools::Itertools;
+use regex::bytes::Regex;
+
+pub fn run_with_params(input: &str, params: &aoc::Params) -> aoc::Answer {
+    let width = params.get("width", 101)?;
+    let height = params.get("height", 103)?;
+    // Rooms without an easter egg, like the sample one, can skip part 2.
+    let part2 = params.get("part2", true)?;
+    let robots = parse_robots(input)?;
+    let safety_factor = get_safety_factor(&robots, width, height);
+    if !part2 {
+        return aoc::answers(safety_factor, aoc::SKIPPED);
+    }
+    ensure!(
+        width >= 31 && height >= 33,
+        "the easter egg doesn't fit in a {width}x{height} room (use part2=false to skip part 2)"
+    );
+    let easter_egg = aoc::span("easter egg", || find_easter_egg(&robots, width, height))?;
+    aoc::answers(safety_factor, easter_egg)
+}
+
+pub type Point = (i64, i64);
+
+pub fn parse_robots(input: &str) -> aoc::Result<Vec<(Point, Point)>> {
+    let parse_robot = |s| {
+        let numbers = aoc::parse_numbers(s)?;
+        let [x, y, vx, vy] = numbers[..].try_into().context_at(s, "expected 4 numbers")?;
+        Ok(((x, y), (vx, vy)))
+    };
+    input.lines().map(parse_robot).try_collect()
+}
+
+pub fn get_safety_factor(robots: &[(Point, Point)], width: i64, height: i64) -> usize {
+    let final_positions = robots
+        .iter()
+        .map(|r| robot_position_after(r, 100, width, height));
+    let quadrant_counts = final_positions
+        .map(|(x, y)| ((x - width / 2).signum(), (y - height / 2).signum()))
+        .filter(|&(x_cmp, y_cmp)| x_cmp != 0 && y_cmp != 0)
+        .counts();
+    quadrant_counts.values().product()
+}
+
+fn robot_position_after(robot: &(Point, Point), seconds: i64, width: i64, height: i64) -> Point {
+    let &((x, y), (vx, vy)) = robot;
+    let final_x = (x + seconds * vx).rem_euclid(width);
+    let final_y = (y + seconds * vy).rem_euclid(height);
+    (final_x, final_y)
+}
+
+// The picture that the robots make at some point, framed.
+pub const EASTER_EGG: [&str; 33] = [
+    "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+    "x                             x",
+    "x                             x",
+    "x                             x",
+    "x                             x",
+    "x              x              x",
+    "x             xxx             x",
+    "x            xxxxx            x",
+    "x           xxxxxxx           x",
+    "x          xxxxxxxxx          x",
+    "x            xxxxx            x",
+    "x           xxxxxxx           x",
+    "x          xxxxxxxxx          x",
+    "x         xxxxxxxxxxx         x",
+    "x        xxxxxxxxxxxxx        x",
+    "x          xxxxxxxxx          x",
+    "x         xxxxxxxxxxx         x",
+    "x        xxxxxxxxxxxxx        x",
+    "x       xxxxxxxxxxxxxxx       x",
+    "x      xxxxxxxxxxxxxxxxx      x",
+    "x        xxxxxxxxxxxxx        x",
+    "x       xxxxxxxxxxxxxxx       x",
+    "x      xxxxxxxxxxxxxxxxx      x",
+    "x     xxxxxxxxxxxxxxxxxxx     x",
+    "x    xxxxxxxxxxxxxxxxxxxxx    x",
+    "x             xxx             x",
+    "x             xxx             x",
+    "x             xxx             x",
+    "x                             x",
+    "x                             x",
+    "x                             x",
+    "x                             x",
+    "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx",
+];
+
+pub fn find_easter_egg(robots: &[(Point, Point)], width: i64, height: i64) -> aoc::Result<i64> {
+    let easter_egg_re = Regex::new(&EASTER_EGG.join(".*")).unwrap();
+    let mut room = vec![b' '; (width * height) as usize];
+    for seconds in 1..1_000_000 {
+        room.fill(b' ');
+        for robot in robots {
+            let (x, y) = robot_position_after(robot, seconds, width, height);
+            room[(y * width + x) as usize] = b'x'
+        }
+        if easter_egg_re.is_match(&room) {
+            return Ok(seconds);
+        }
+    }
+    bail!("easter egg not found")
+}
+
+#[test]
+fn sample_test() {
+    let sample = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+    let robots = parse_robots(sample).unwrap();
+    assert_eq!(get_safety_factor(&robots, 11, 7), 12)
+}
@@ -4,7 +4,7 @@ use pathfinding::prelude::bfs_reach;
 use rustc_hash::FxHashMap as HashMap;
 
 pub fn run(input: &str) -> aoc::Answer {
-    let (map, instructions) = input.split_once("\n\n").context("invalid input")?;
+    let (map, instructions) = aoc::split_sections(input)?;
     let (map, w, h) = aoc::parse_char_grid(map)?;
     let map = iproduct!(0..w, 0..h)
         .map(|(x, y)| ((x as i64, y as i64), map[y][x]))
@@ -12,8 +12,8 @@ pub fn run(input: &str) -> aoc::Answer {
         .collect();
     let wide_map = widen_map(&map);
     aoc::answers(
-        run_robot(map, instructions)?,
-        run_robot(wide_map, instructions)?,
+        aoc::span("part 1", || run_robot(map, instructions))?,
+        aoc::span("part 2", || run_robot(wide_map, instructions))?,
     )
 }
 
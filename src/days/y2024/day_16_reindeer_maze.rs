@@ -21,26 +21,31 @@ pub fn run(input: &str) -> aoc::Answer {
             .into_iter()
             .filter(|&(((x, y), _), _)| x < w && y < h && maze[y][x] != '#')
     };
-    let (parents, end) = dijkstra_partial(&start, successors, |&((x, y), _)| maze[y][x] == 'E');
+    let (parents, end) = aoc::span("dijkstra", || {
+        dijkstra_partial(&start, successors, |&((x, y), _)| maze[y][x] == 'E')
+    });
     let end = end.context("path to end not found")?;
     let best_score = parents[&end].1;
 
     // Reconstruct all possible best paths by looking for nodes that connect to the best path and
     // have the same cost at the point of connection.
     let mut best_paths_nodes = HashSet::from_iter(build_path(&end, &parents));
-    loop {
-        let join_node = parents.iter().find(|(node, (_parent, node_cost))| {
-            !best_paths_nodes.contains(node)
-                && successors(node).any(|(succ_node, succ_cost)| {
-                    // `node` joins a best path with same cost, so it's also part of a best path.
-                    best_paths_nodes.contains(&succ_node)
-                        && succ_node != start
-                        && node_cost + succ_cost == parents[&succ_node].1
-                })
-        });
-        let Some((node, _)) = join_node else { break };
-        best_paths_nodes.extend(build_path(node, &parents));
-    }
+    aoc::span("best paths join", || {
+        loop {
+            let join_node = parents.iter().find(|(node, (_parent, node_cost))| {
+                !best_paths_nodes.contains(node)
+                    && successors(node).any(|(succ_node, succ_cost)| {
+                        // `node` joins a best path with same cost, so it's also part of a best
+                        // path.
+                        best_paths_nodes.contains(&succ_node)
+                            && succ_node != start
+                            && node_cost + succ_cost == parents[&succ_node].1
+                    })
+            });
+            let Some((node, _)) = join_node else { break };
+            best_paths_nodes.extend(build_path(node, &parents));
+        }
+    });
     let best_paths_tiles: HashSet<_> = best_paths_nodes.iter().map(|&(pos, _)| pos).collect();
 
     aoc::answers(best_score, best_paths_tiles.len())
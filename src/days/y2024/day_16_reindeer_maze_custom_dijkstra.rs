@@ -8,7 +8,6 @@ use std::hash::Hash;
 
 // Alternative day 16 solution, implementing an ad-hoc Dijkstra algorithm, which works similar to
 // `dijkstra_partial` from the `pathfinding` crate, but keeping track of multiple shortest paths.
-#[allow(dead_code)]
 pub fn run(input: &str) -> aoc::Answer {
     let (maze, w, h) = aoc::parse_char_grid(input)?;
     let start_pos = iproduct!(0..w, 0..h)
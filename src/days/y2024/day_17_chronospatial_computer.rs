@@ -1,4 +1,5 @@
 use anyhow::{Context, ensure};
+use aoc::InputContext;
 use itertools::Itertools;
 use pathfinding::prelude::bfs;
 
@@ -46,7 +47,7 @@ pub fn run(input: &str) -> aoc::Answer {
     aoc::answers(output, min_a)
 }
 
-fn run_program(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64> {
+pub fn run_program(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64> {
     let mut ip = 0;
     let mut outputs = vec![];
     while ip + 1 < program.len() {
@@ -62,11 +63,9 @@ fn run_program(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64>
             0 => a >>= combo_op,
             1 => b ^= operand,
             2 => b = combo_op % 8,
-            3 => {
-                if a != 0 {
-                    ip = operand as usize;
-                    continue;
-                }
+            3 if a != 0 => {
+                ip = operand as usize;
+                continue;
             }
             4 => b ^= c,
             5 => outputs.push(combo_op % 8),
@@ -79,10 +78,12 @@ fn run_program(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64>
     outputs
 }
 
-fn parse_program(input: &str) -> aoc::Result<(u64, u64, u64, Vec<u64>)> {
-    let (registers, program) = input.split_once("\n\n").context("invalid input")?;
-    let registers = aoc::parse_numbers(registers)?;
-    let [a, b, c] = registers[..].try_into().context("expected 3 registers")?;
+pub fn parse_program(input: &str) -> aoc::Result<(u64, u64, u64, Vec<u64>)> {
+    let (registers_part, program) = aoc::split_sections(input)?;
+    let registers = aoc::parse_numbers(registers_part)?;
+    let [a, b, c] = registers[..]
+        .try_into()
+        .context_at(registers_part, "expected 3 registers")?;
     let program = aoc::parse_numbers(program)?;
     Ok((a, b, c, program))
 }
@@ -1,27 +1,36 @@
 use anyhow::Context;
+use aoc::InputContext;
 use itertools::Itertools;
 use pathfinding::prelude::bfs;
 use rustc_hash::FxHashSet as HashSet;
 
-pub fn run(input: &str) -> aoc::Answer {
+pub fn run_with_params(input: &str, params: &aoc::Params) -> aoc::Answer {
+    let memory_size = params.get("memory_size", 70)?;
+    let fallen_count = params.get("fallen_bytes", 1024)?;
     let falling_bytes = parse_byte_coordinates(input)?;
-    let step_count = find_path(&falling_bytes[0..1024], 70).context("path to exit not found")?;
-    let (bx, by) = find_first_blocking_byte(&falling_bytes, 70)
-        .context("no byte that blocks path to exit found")?;
+    let fallen_bytes = falling_bytes
+        .get(0..fallen_count)
+        .context("not enough falling bytes")?;
+    let step_count = aoc::span("part 1", || find_path(fallen_bytes, memory_size));
+    let step_count = step_count.context("path to exit not found")?;
+    let blocking_byte = aoc::span("part 2", || {
+        find_first_blocking_byte(&falling_bytes, memory_size)
+    });
+    let (bx, by) = blocking_byte.context("no byte that blocks path to exit found")?;
     aoc::answers(step_count, format!("{bx},{by}"))
 }
 
-fn parse_byte_coordinates(input: &str) -> aoc::Result<Vec<(u32, u32)>> {
+pub fn parse_byte_coordinates(input: &str) -> aoc::Result<Vec<(u32, u32)>> {
     let parse_coordinates = |l| {
         aoc::parse_numbers(l)?
             .into_iter()
             .collect_tuple()
-            .context("expected two numbers")
+            .context_at(l, "expected two numbers")
     };
     input.lines().map(parse_coordinates).try_collect()
 }
 
-fn find_path(fallen_bytes: &[(u32, u32)], memory_size: u32) -> Option<usize> {
+pub fn find_path(fallen_bytes: &[(u32, u32)], memory_size: u32) -> Option<usize> {
     let corrupted_positions: HashSet<_> = fallen_bytes.iter().copied().collect();
     let path = bfs(
         &(0u32, 0u32),
@@ -38,7 +47,7 @@ fn find_path(fallen_bytes: &[(u32, u32)], memory_size: u32) -> Option<usize> {
     Some(path.len() - 1)
 }
 
-fn find_first_blocking_byte(bytes: &[(u32, u32)], memory_size: u32) -> Option<(u32, u32)> {
+pub fn find_first_blocking_byte(bytes: &[(u32, u32)], memory_size: u32) -> Option<(u32, u32)> {
     let indices = (0..bytes.len()).collect_vec();
     let idx = indices.partition_point(|&i| find_path(&bytes[0..=i], memory_size).is_some());
     bytes.get(idx).copied()
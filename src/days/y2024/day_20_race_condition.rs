@@ -3,12 +3,16 @@ use itertools::iproduct;
 use pathfinding::prelude::bfs;
 use rustc_hash::FxHashMap as HashMap;
 
-pub fn run(input: &str) -> aoc::Answer {
+pub fn run_with_params(input: &str, params: &aoc::Params) -> aoc::Answer {
+    let min_save_time = params.get("min_save_time", 100)?;
     let path = find_path(input)?;
-    aoc::answers(count_cheats(&path, 2, 100), count_cheats(&path, 20, 100))
+    aoc::answers(
+        count_cheats(&path, 2, min_save_time),
+        count_cheats(&path, 20, min_save_time),
+    )
 }
 
-fn find_path(input: &str) -> aoc::Result<Vec<(usize, usize)>> {
+pub fn find_path(input: &str) -> aoc::Result<Vec<(usize, usize)>> {
     let (ref map, w, h) = aoc::parse_char_grid(input)?;
     let start = iproduct!(0..w, 0..h)
         .find(|&(x, y)| map[y][x] == 'S')
@@ -22,7 +26,7 @@ fn find_path(input: &str) -> aoc::Result<Vec<(usize, usize)>> {
     bfs(&start, successors, |&(x, y)| map[y][x] == 'E').context("path to the end not found")
 }
 
-fn count_cheats(path: &[(usize, usize)], max_cheat: isize, min_save_time: isize) -> usize {
+pub fn count_cheats(path: &[(usize, usize)], max_cheat: isize, min_save_time: isize) -> usize {
     let times: HashMap<_, _> = path.iter().copied().zip(0..).collect();
     let mut cheat_count = 0;
     for (&(x, y), &t) in &times {
@@ -8,7 +8,7 @@ pub fn run(input: &str) -> aoc::Answer {
     )
 }
 
-fn get_complexities_sum(input: &str, dir_robot_count: usize) -> usize {
+pub fn get_complexities_sum(input: &str, dir_robot_count: usize) -> usize {
     let num_pad = &build_keypad("789\n456\n123\n 0A");
     let dir_pad = &build_keypad(" ^A\n<v>");
     let keypad_chain = [vec![num_pad], vec![dir_pad; dir_robot_count]].concat();
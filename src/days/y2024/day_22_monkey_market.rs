@@ -1,29 +1,44 @@
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use std::hash::Hash;
 
 pub fn run(input: &str) -> aoc::Answer {
     let secrets = aoc::parse_numbers(input)?;
+    // Store 4 price diffs as 4 bytes in a u32. Using a [u8; 4] is slower for some reason.
+    let (secrets_sum, max_bananas) =
+        simulate_buyers(&secrets, 0_u32, |diffs, diff| diffs << 8 | diff);
+    aoc::answers(secrets_sum, max_bananas)
+}
+
+// Returns the sum of the buyers' secret numbers after 2000 changes, and the most bananas that can
+// be bought with a sequence of 4 price diffs. The last 4 diffs are kept as a `D`, which starts as
+// `no_diffs` and gets each new diff with `push_diff`.
+pub fn simulate_buyers<D: Copy + Eq + Hash>(
+    secrets: &[u32],
+    no_diffs: D,
+    push_diff: impl Fn(D, u32) -> D,
+) -> (u64, u32) {
     let mut secrets_sum = 0_u64;
     let mut total_bananas = HashMap::default();
-    for mut secret in secrets {
-        // Store 4 price diffs as 4 bytes in a u32. Using a [u8; 4] is slower for some reason.
-        let mut diffs = 0_u32;
+    for &secret in secrets {
+        let mut secret = secret;
+        let mut diffs = no_diffs;
         let mut diffs_seen = HashSet::default();
         for i in 0..2000 {
             let prev_secret = secret;
             secret = rand(secret);
             let diff = 10 + secret % 10 - prev_secret % 10;
-            diffs = diffs << 8 | diff;
+            diffs = push_diff(diffs, diff);
             if i >= 3 && diffs_seen.insert(diffs) {
                 *total_bananas.entry(diffs).or_insert(0) += secret % 10;
             }
         }
         secrets_sum += secret as u64;
     }
-    let max_bananas = total_bananas.values().max().unwrap_or(&0);
-    aoc::answers(secrets_sum, max_bananas)
+    let max_bananas = total_bananas.into_values().max().unwrap_or(0);
+    (secrets_sum, max_bananas)
 }
 
-fn rand(mut s: u32) -> u32 {
+pub fn rand(mut s: u32) -> u32 {
     let mask = 0xFFFFFF;
     s = ((s << 6) ^ s) & mask;
     s = ((s >> 5) ^ s) & mask;
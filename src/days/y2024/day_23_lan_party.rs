@@ -1,4 +1,4 @@
-use anyhow::Context;
+use aoc::InputContext;
 use itertools::Itertools;
 use pathfinding::prelude::maximal_cliques_collect;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
@@ -23,10 +23,12 @@ pub fn run(input: &str) -> aoc::Answer {
     aoc::answers(triplets_count, password)
 }
 
-fn parse_connections(input: &str) -> aoc::Result<HashMap<&str, HashSet<&str>>> {
+pub fn parse_connections(input: &str) -> aoc::Result<HashMap<&str, HashSet<&str>>> {
     let mut conns = HashMap::<&str, HashSet<&str>>::default();
     for line in input.lines() {
-        let (a, b) = line.split_once('-').context("invalid input line")?;
+        let (a, b) = line
+            .split_once('-')
+            .context_at(line, "invalid input line")?;
         conns.entry(a).or_default().insert(b);
         conns.entry(b).or_default().insert(a);
     }
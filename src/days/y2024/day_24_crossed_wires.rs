@@ -1,4 +1,4 @@
-use anyhow::{Context, bail};
+use aoc::InputContext;
 use itertools::Itertools;
 use rustc_hash::FxHashMap as HashMap;
 
@@ -11,23 +11,21 @@ pub fn run(input: &str) -> aoc::Answer {
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-enum Op {
+pub enum Op {
     And,
     Or,
     Xor,
 }
 use Op::*;
 
-type WireMap<'a, T> = HashMap<&'a str, T>;
-type Gate<'a> = (Op, &'a str, &'a str);
+pub type WireMap<'a, T> = HashMap<&'a str, T>;
+pub type Gate<'a> = (Op, &'a str, &'a str);
 
-fn parse_wires(input: &str) -> aoc::Result<(WireMap<bool>, WireMap<Gate>)> {
-    let (inputs, gates) = input
-        .split_once("\n\n")
-        .context("section separator not found")?;
+pub fn parse_wires(input: &str) -> aoc::Result<(WireMap<'_, bool>, WireMap<'_, Gate<'_>>)> {
+    let (inputs, gates) = aoc::split_sections(input)?;
 
     let inputs = inputs.lines().map(|line| -> aoc::Result<_> {
-        let (name, value) = line.split_once(": ").context("invalid line")?;
+        let (name, value) = line.split_once(": ").context_at(line, "invalid line")?;
         Ok((name, value == "1"))
     });
 
@@ -36,14 +34,14 @@ fn parse_wires(input: &str) -> aoc::Result<(WireMap<bool>, WireMap<Gate>)> {
             Some((a, "AND", b, "->", out)) => (out, (And, a, b)),
             Some((a, "OR", b, "->", out)) => (out, (Or, a, b)),
             Some((a, "XOR", b, "->", out)) => (out, (Xor, a, b)),
-            _ => bail!("invalid gate line '{line}'"),
+            _ => return Err(aoc::input_error(line, "invalid gate line")),
         })
     });
 
     Ok((inputs.try_collect()?, gates.try_collect()?))
 }
 
-fn get_numeric_output(inputs: &WireMap<bool>, gates: &WireMap<Gate>) -> u64 {
+pub fn get_numeric_output(inputs: &WireMap<bool>, gates: &WireMap<Gate>) -> u64 {
     gates
         .keys()
         .filter(|name| name.starts_with('z'))
@@ -75,40 +73,44 @@ fn get_value(name: &str, inputs: &WireMap<bool>, gates: &WireMap<Gate>) -> bool
 /// This circuit can be broken down into 45 1-bit adders. The first of which only consists of an AND
 /// and an OR gate, and should look like this:
 ///
-///     ┌───┐       ┌───┐   ┌───┐
-///     │x00├──┬────┤XOR├───┤z00│
-///     └───┘  │ ┌──┤   │   └───┘
-///            │ │  └───┘
-///     ┌───┐  │ │  ┌───┐
-///     │y00├────┴──┤AND├─────┐
-///     └───┘  └────┤   │     │
-///                 └───┘    z00
-///                         carry
+/// ```text
+/// ┌───┐       ┌───┐   ┌───┐
+/// │x00├──┬────┤XOR├───┤z00│
+/// └───┘  │ ┌──┤   │   └───┘
+///        │ │  └───┘
+/// ┌───┐  │ │  ┌───┐
+/// │y00├────┴──┤AND├─────┐
+/// └───┘  └────┤   │     │
+///             └───┘    z00
+///                     carry
+/// ```
 ///
 /// The other 44 1-bit adders also take the carry bit from the previous adder. They are made of two
 /// XOR, two AND, and an OR gate. For example, the x10 and y10 adder should look like this:
 ///
-///       z09
-///      carry────────────────┐
-///                           │
-///     ┌───┐       ┌───┐     │  ┌───┐     ┌───┐
-///     │x10├──┬────┤XOR├───┬────┤XOR├─────┤z10│
-///     └───┘  │ ┌──┤   │   │ ├──┤   │     └───┘
-///            │ │  └───┘   │ │  └───┘
-///     ┌───┐  │ │  ┌───┐   │ │  ┌───┐
-///     │y10├────┴──┤AND├─┐ │ └──┤AND├─┐
-///     └───┘  └────┤   │ │ └────┤   │ │ ┌───┐
-///                 └───┘ │      └───┘ └─┤OR ├──┐
-///                       └──────────────┤   │  │
-///                                      └───┘ z10
-///                                           carry
+/// ```text
+///   z09
+///  carry────────────────┐
+///                       │
+/// ┌───┐       ┌───┐     │  ┌───┐     ┌───┐
+/// │x10├──┬────┤XOR├───┬────┤XOR├─────┤z10│
+/// └───┘  │ ┌──┤   │   │ ├──┤   │     └───┘
+///        │ │  └───┘   │ │  └───┘
+/// ┌───┐  │ │  ┌───┐   │ │  ┌───┐
+/// │y10├────┴──┤AND├─┐ │ └──┤AND├─┐
+/// └───┘  └────┤   │ │ └────┤   │ │ ┌───┐
+///             └───┘ │      └───┘ └─┤OR ├──┐
+///                   └──────────────┤   │  │
+///                                  └───┘ z10
+///                                       carry
+/// ```
 ///
-/// Diagrams made with ASCIIFlow (https://asciiflow.com)
+/// Diagrams made with [ASCIIFlow](https://asciiflow.com).
 ///
 /// Note: This function is not general by any means. It only works for the special case of this
 /// specific 45-bit full adder circuit. And it probably doesn't even detect all possible wire swaps,
 /// but it works for the given input file.
-fn get_swapped_wires(gates: &WireMap<Gate>) -> String {
+pub fn get_swapped_wires(gates: &WireMap<Gate>) -> String {
     let mut output_gates: WireMap<Vec<Op>> = WireMap::default();
     for (&name, &(op, a, b)) in gates {
         output_gates.entry(name).or_default();
@@ -150,13 +152,15 @@ fn bad_inputs_test() {
         run("bad input\n\n").unwrap_err().to_string(),
         "invalid line"
     );
+    // Errors point at the offending line, which the message doesn't need to repeat.
+    let located_error = |input| aoc::locate_error(run(input).unwrap_err(), input, "in.txt");
     assert_eq!(
-        run("x0: 1\n\nbad gate").unwrap_err().to_string(),
-        "invalid gate line 'bad gate'"
+        located_error("x0: 1\n\nbad gate").to_string(),
+        "in.txt:3:1: invalid gate line\n3 | bad gate\n  | ^^^^^^^^"
     );
     assert_eq!(
-        run("x0: 1\n\nx0 NARF x0 -> z0").unwrap_err().to_string(),
-        "invalid gate line 'x0 NARF x0 -> z0'"
+        located_error("x0: 1\n\nx0 NARF x0 -> z0").to_string(),
+        "in.txt:3:1: invalid gate line\n3 | x0 NARF x0 -> z0\n  | ^^^^^^^^^^^^^^^^"
     );
 }
 
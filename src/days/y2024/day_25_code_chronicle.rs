@@ -2,7 +2,17 @@ use itertools::iproduct;
 use std::iter::zip;
 
 pub fn run(input: &str) -> aoc::Answer {
-    let (locks, keys): (Vec<_>, Vec<_>) = input.split("\n\n").partition(|s| s.starts_with("#####"));
+    let mut locks = vec![];
+    let mut keys = vec![];
+    for schematic in aoc::sections(input) {
+        if schematic.starts_with("#####") {
+            locks.push(schematic);
+        } else if schematic.ends_with("#####") {
+            keys.push(schematic);
+        } else {
+            return Err(aoc::input_error(schematic, "expected a lock or a key"));
+        }
+    }
     let fitting_pairs = iproduct!(locks, keys).filter(|(lock, key)| {
         !zip(lock.chars(), key.chars()).any(|(c1, c2)| c1 == '#' && c2 == '#')
     });
@@ -53,3 +63,9 @@ fn sample_test() {
 ";
     assert_eq!(run(sample).unwrap(), "3")
 }
+
+#[test]
+fn bad_schematic_test() {
+    let err = run("#####\n.....\n\n.....\n.....\n").unwrap_err();
+    assert_eq!(err.to_string(), "expected a lock or a key");
+}
@@ -0,0 +1,8 @@
+// The puzzle inputs baked into the executable by build.rs, with the embed-inputs feature.
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+// The embedded input of a day, if there is one.
+pub fn input(year: usize, day_num: usize) -> Option<&'static str> {
+    let input = INPUTS.iter().find(|&&(y, d, _)| y == year && d == day_num);
+    input.map(|&(_, _, input)| input)
+}
@@ -0,0 +1,410 @@
+use crate::output::json_string;
+use crate::rng::Rng;
+use crate::runner;
+use crate::samples::{self, Sample};
+use anyhow::{Context, ensure};
+use aoc::days::Solver;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
+
+pub const DEFAULT_RUNS: usize = 1000;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+const FAILURES_DIR: &str = "fuzz-failures";
+
+// How long to spend minimizing each failing input.
+const MINIMIZE_TIME: Duration = Duration::from_secs(10);
+
+// Numbers that often find their way into edge cases.
+const INTERESTING_NUMBERS: &[&str] = &[
+    "0",
+    "1",
+    "-1",
+    "2",
+    "3",
+    "10",
+    "255",
+    "65536",
+    "4294967296",
+    "9223372036854775807",
+    "99999999999999999999",
+];
+
+// Characters that may be inserted on any input, besides the ones that the day's samples have.
+const EXTRA_CHARS: &str = "\n ,-:0123456789";
+
+pub struct Settings {
+    pub year: usize,
+    pub variant: Option<String>,
+    pub runs: usize,
+    pub seed: u64,
+    pub timeout: Duration,
+}
+
+// An input that makes a day panic, crash or hang. Failures with the same signature, like panics at
+// the same location, are considered the same.
+#[derive(Debug, Clone, PartialEq)]
+struct Failure {
+    signature: String,
+    message: String,
+}
+
+// Feeds each of the given days with `runs` random inputs, and reports the ones that make it panic,
+// overflow its stack or hang, along with a minimized input that reproduces each failure. Inputs are
+// mostly mutations of the day's samples, so they get past the parsing, and they run on a child
+// process so that crashes and hangs can be detected. Day errors are not failures.
+pub fn fuzz_days(day_nums: &[usize], settings: &Settings) -> aoc::Result<()> {
+    println!(
+        "Fuzzing with seed {} (use --seed {} to repeat)",
+        settings.seed, settings.seed
+    );
+    let mut failed_days = 0;
+    for &day_num in day_nums {
+        // Days without samples still get random inputs.
+        let samples_dir = samples::samples_dir(settings.year);
+        let samples = samples::load_samples(&samples_dir, &[day_num]).unwrap_or_default();
+        let mut rng = Rng::new(settings.seed ^ (day_num as u64).wrapping_mul(0x9e3779b97f4a7c15));
+        let mut executor = Executor::new(day_num, settings);
+
+        let mut failures: Vec<(Failure, String, aoc::Params)> = vec![];
+        for _ in 0..settings.runs {
+            let (input, params) = generate_input(&mut rng, &samples);
+            if let Some(failure) = executor.run(&input, &params)?
+                && failures
+                    .iter()
+                    .all(|(f, ..)| f.signature != failure.signature)
+            {
+                failures.push((failure, input, params));
+            }
+        }
+
+        let day = format!("Day {day_num}");
+        if failures.is_empty() {
+            println!("{day:<7} OK    {} inputs", settings.runs);
+            continue;
+        }
+        failed_days += 1;
+        for (i, (failure, input, params)) in failures.into_iter().enumerate() {
+            println!("{day:<7} FAIL  {}", failure.message);
+            let input = minimize(&input, |candidate| {
+                let result = executor.run(candidate, &params);
+                result.is_ok_and(|f| f.is_some_and(|f| f.signature == failure.signature))
+            });
+            let path = format!(
+                "{FAILURES_DIR}/{}-{day_num:02}-{}.txt",
+                settings.year,
+                i + 1
+            );
+            fs::create_dir_all(FAILURES_DIR)
+                .and_then(|_| fs::write(&path, &input))
+                .with_context(|| format!("error writing {path}"))?;
+            let params = if params.is_empty() {
+                String::new()
+            } else {
+                format!(", with params {params}")
+            };
+            println!(
+                "{:<7}       reproducer: {}{params}",
+                "",
+                json_string(&input)
+            );
+            println!("{:<7}       saved to {path}", "");
+        }
+    }
+    ensure!(
+        failed_days == 0,
+        "{failed_days} of {} days failed",
+        day_nums.len()
+    );
+    Ok(())
+}
+
+// Runs inputs on a worker child process, which is restarted after crashes and hangs.
+struct Executor {
+    day_num: usize,
+    year: usize,
+    variant: Option<String>,
+    timeout: Duration,
+    worker: Option<Worker>,
+}
+
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    responses: Receiver<String>,
+}
+
+impl Executor {
+    fn new(day_num: usize, settings: &Settings) -> Executor {
+        Executor {
+            day_num,
+            year: settings.year,
+            variant: settings.variant.clone(),
+            timeout: settings.timeout,
+            worker: None,
+        }
+    }
+
+    // Runs the day on the given input and returns how it failed, if it did.
+    fn run(&mut self, input: &str, params: &aoc::Params) -> aoc::Result<Option<Failure>> {
+        let mut worker = match self.worker.take() {
+            Some(worker) => worker,
+            None => self.spawn_worker()?,
+        };
+        // If the worker is gone, this fails and the lack of response tells why.
+        _ = write!(worker.stdin, "{params}\n{}\n{input}", input.len())
+            .and_then(|_| worker.stdin.flush());
+        match worker.responses.recv_timeout(self.timeout) {
+            Ok(response) => {
+                self.worker = Some(worker);
+                Ok(parse_response(&response))
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                _ = worker.child.kill();
+                _ = worker.child.wait();
+                let message = format!("hung for more than {:?}", self.timeout);
+                Ok(Some(Failure {
+                    signature: "hang".to_string(),
+                    message,
+                }))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                drop(worker.stdin);
+                let output = worker.child.wait_with_output()?;
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let message = if stderr.contains("has overflowed its stack") {
+                    "stack overflow".to_string()
+                } else {
+                    format!("crashed ({})", output.status)
+                };
+                Ok(Some(Failure {
+                    signature: message.clone(),
+                    message,
+                }))
+            }
+        }
+    }
+
+    fn spawn_worker(&self) -> aoc::Result<Worker> {
+        let exe = env::current_exe().context("error finding the aoc executable")?;
+        let mut command = Command::new(exe);
+        command.args([self.year.to_string(), self.day_num.to_string()]);
+        command.arg("--fuzz-worker");
+        if let Some(variant) = &self.variant {
+            command.args(["--variant", variant]);
+        }
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("error starting fuzz worker")?;
+        let stdin = child.stdin.take().context("no worker stdin")?;
+        let stdout = child.stdout.take().context("no worker stdout")?;
+        let (sender, responses) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Worker {
+            child,
+            stdin,
+            responses,
+        })
+    }
+}
+
+// Parses the response of a worker for an input: "ok", or "panic" followed by the panic message.
+fn parse_response(response: &str) -> Option<Failure> {
+    let message = response.strip_prefix("panic ")?;
+    // The location of panics identifies them; their messages may depend on the input.
+    let signature = message.split_once(": ").map_or(message, |(loc, _)| loc);
+    Some(Failure {
+        signature: signature.to_string(),
+        message: message.to_string(),
+    })
+}
+
+// The worker side of fuzzing, run by the child process. Reads inputs from stdin, each one as a line
+// with the params, a line with the input length in bytes, and the input itself, runs the day on them
+// and responds with a line for each one on stdout.
+pub fn run_worker(solver: Solver) -> aoc::Result<()> {
+    // Runs on a day thread, like the days do normally, so panics are caught by the panic hook.
+    let name = format!("{}fuzz worker", runner::DAY_THREAD_PREFIX);
+    let worker = thread::Builder::new().name(name).spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout().lock();
+        loop {
+            let mut params = String::new();
+            if stdin.read_line(&mut params)? == 0 {
+                return Ok(());
+            }
+            let params: aoc::Params = params.parse()?;
+            let mut len = String::new();
+            stdin.read_line(&mut len)?;
+            let mut input = vec![0; len.trim().parse()?];
+            stdin.read_exact(&mut input)?;
+            let input = String::from_utf8(input)?;
+
+            let response = match runner::catch_panic(|| solver(&input, &params)) {
+                Ok(_) => "ok".to_string(),
+                Err(message) => format!("panic {}", message.replace('\n', " ")),
+            };
+            writeln!(stdout, "{response}")?;
+            stdout.flush()?;
+        }
+    })?;
+    worker.join().unwrap_or_else(|_| Ok(()))
+}
+
+fn generate_input(rng: &mut Rng, samples: &[Sample]) -> (String, aoc::Params) {
+    if samples.is_empty() {
+        let alphabet: Vec<_> = EXTRA_CHARS.chars().collect();
+        return (random_text(rng, &alphabet), aoc::Params::default());
+    }
+    let sample = &samples[rng.below(samples.len())];
+    // Characters are picked with the same frequency they have on the sample.
+    let alphabet: Vec<_> = sample.input.chars().chain(EXTRA_CHARS.chars()).collect();
+    if rng.below(8) == 0 {
+        return (random_text(rng, &alphabet), sample.params.clone());
+    }
+    let mut chars: Vec<_> = sample.input.chars().collect();
+    for _ in 0..=rng.below(4) {
+        mutate(rng, &mut chars, &alphabet);
+    }
+    (chars.into_iter().collect(), sample.params.clone())
+}
+
+fn random_text(rng: &mut Rng, alphabet: &[char]) -> String {
+    let len = rng.below(64);
+    (0..len)
+        .map(|_| alphabet[rng.below(alphabet.len())])
+        .collect()
+}
+
+fn mutate(rng: &mut Rng, chars: &mut Vec<char>, alphabet: &[char]) {
+    let pos = rng.below(chars.len() + 1);
+    let line = line_at(chars, pos);
+    match rng.below(7) {
+        // Replace a number with an interesting one, or a random one.
+        0 => {
+            let Some(number) = number_at(chars, pos) else {
+                return;
+            };
+            let replacement = match rng.below(INTERESTING_NUMBERS.len() + 1) {
+                i if i < INTERESTING_NUMBERS.len() => INTERESTING_NUMBERS[i].to_string(),
+                _ => (rng.next() % 10u64.pow(rng.below(8) as u32 + 1)).to_string(),
+            };
+            chars.splice(number, replacement.chars());
+        }
+        1 => chars.insert(pos, alphabet[rng.below(alphabet.len())]),
+        2 if pos < chars.len() => chars[pos] = alphabet[rng.below(alphabet.len())],
+        3 => {
+            let end = chars.len().min(pos + 1 + rng.below(8));
+            chars.drain(pos..end);
+        }
+        4 => {
+            let line = chars[line.clone()].to_vec();
+            chars.splice(pos..pos, line);
+        }
+        5 => {
+            chars.drain(line);
+        }
+        _ => chars.truncate(pos),
+    }
+}
+
+// The range of the line at the given position, including its newline.
+fn line_at(chars: &[char], pos: usize) -> std::ops::Range<usize> {
+    let start = chars[..pos]
+        .iter()
+        .rposition(|&ch| ch == '\n')
+        .map_or(0, |i| i + 1);
+    let end = chars[pos..]
+        .iter()
+        .position(|&ch| ch == '\n')
+        .map_or(chars.len(), |i| pos + i + 1);
+    start..end
+}
+
+// The range of the first number at or after the given position, if any.
+fn number_at(chars: &[char], pos: usize) -> Option<std::ops::Range<usize>> {
+    let start = pos + chars[pos..].iter().position(|ch| ch.is_ascii_digit())?;
+    let len = chars[start..]
+        .iter()
+        .take_while(|ch| ch.is_ascii_digit())
+        .count();
+    Some(start..start + len)
+}
+
+// Shrinks a failing input by removing chunks of lines, and then of characters, for as long as it
+// keeps failing (or until `MINIMIZE_TIME` runs out).
+fn minimize(input: &str, mut still_fails: impl FnMut(&str) -> bool) -> String {
+    let start = Instant::now();
+    let mut units: Vec<String> = input.split_inclusive('\n').map(String::from).collect();
+    for by_chars in [false, true] {
+        if by_chars {
+            units = units.concat().chars().map(String::from).collect();
+        }
+        let mut chunk_len = units.len() / 2;
+        while chunk_len > 0 {
+            let mut i = 0;
+            while i < units.len() && start.elapsed() < MINIMIZE_TIME {
+                let end = units.len().min(i + chunk_len);
+                let candidate = [&units[..i], &units[end..]].concat();
+                if still_fails(&candidate.concat()) {
+                    units = candidate;
+                } else {
+                    i += chunk_len;
+                }
+            }
+            chunk_len /= 2;
+        }
+    }
+    units.concat()
+}
+
+#[test]
+fn minimize_test() {
+    let input = "aaa\nbXb\nccc\n";
+    assert_eq!(minimize(input, |s| s.contains('X')), "X");
+    assert_eq!(minimize(input, |s| s.lines().count() >= 2), "\n\n");
+    assert_eq!(minimize(input, |_| false), input);
+}
+
+#[test]
+fn mutate_test() {
+    let chars: Vec<_> = "12 34\n56 78\n".chars().collect();
+    assert_eq!(line_at(&chars, 7), 6..12);
+    assert_eq!(line_at(&chars, 12), 12..12);
+    assert_eq!(number_at(&chars, 2), Some(3..5));
+    assert_eq!(number_at(&chars, 12), None);
+
+    // Same seed, same inputs.
+    let generate = |seed| {
+        let mut rng = Rng::new(seed);
+        let mut chars = chars.clone();
+        for _ in 0..10 {
+            mutate(&mut rng, &mut chars, &['x', '\n']);
+        }
+        chars.into_iter().collect::<String>()
+    };
+    assert_eq!(generate(42), generate(42));
+}
+
+#[test]
+fn parse_response_test() {
+    assert_eq!(parse_response("ok"), None);
+    let failure = parse_response("panic panicked at src/x.rs:1:2: index 5 out of bounds").unwrap();
+    assert_eq!(failure.signature, "panicked at src/x.rs:1:2");
+    assert_eq!(
+        failure.message,
+        "panicked at src/x.rs:1:2: index 5 out of bounds"
+    );
+}
@@ -0,0 +1,375 @@
+use crate::rng::Rng;
+use aoc::days::y2024::day_14_restroom_redoubt::EASTER_EGG;
+use itertools::Itertools;
+use rustc_hash::FxHashSet as HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+// Makes random puzzle inputs of a day, well-formed and with answers, for stress testing, checking
+// variants against each other or benchmarking at a larger scale than the real inputs.
+pub struct Generator {
+    pub day_num: usize,
+    // What the size counts, and its default, which is about what real inputs have.
+    pub size_of: &'static str,
+    pub default_size: usize,
+    pub sizes: RangeInclusive<usize>,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+const GENERATORS: &[(usize, &[Generator])] = &[(
+    2024,
+    &[
+        Generator {
+            day_num: 1,
+            size_of: "lines",
+            default_size: 1000,
+            sizes: 1..=100_000,
+            generate: location_lists,
+        },
+        Generator {
+            day_num: 2,
+            size_of: "reports",
+            default_size: 1000,
+            sizes: 1..=100_000,
+            generate: reports,
+        },
+        Generator {
+            day_num: 5,
+            size_of: "updates",
+            default_size: 200,
+            sizes: 1..=100_000,
+            generate: print_queue,
+        },
+        Generator {
+            day_num: 11,
+            size_of: "stones",
+            default_size: 8,
+            sizes: 1..=10_000,
+            generate: stones,
+        },
+        Generator {
+            day_num: 14,
+            size_of: "robots",
+            default_size: 500,
+            sizes: 353..=9000,
+            generate: robots,
+        },
+        Generator {
+            day_num: 16,
+            size_of: "tiles per side",
+            default_size: 141,
+            sizes: 5..=2001,
+            generate: maze,
+        },
+        Generator {
+            day_num: 19,
+            size_of: "designs",
+            default_size: 400,
+            sizes: 1..=100_000,
+            generate: linen_layout,
+        },
+        Generator {
+            day_num: 22,
+            size_of: "buyers",
+            default_size: 2000,
+            sizes: 1..=100_000,
+            generate: secret_numbers,
+        },
+        Generator {
+            day_num: 23,
+            size_of: "computers",
+            default_size: 520,
+            sizes: 50..=676,
+            generate: lan,
+        },
+    ],
+)];
+
+// The generators of a year, in order of day.
+pub fn year_generators(year: usize) -> &'static [Generator] {
+    let generators = GENERATORS.iter().find(|&&(y, _)| y == year);
+    generators.map_or(&[], |&(_, generators)| generators)
+}
+
+// Two columns of 5 digit numbers, where the right one repeats some numbers of the left one.
+fn location_lists(rng: &mut Rng, size: usize) -> String {
+    let left = (0..size).map(|_| rng.between(10000, 99999)).collect_vec();
+    let mut output = String::new();
+    for &l in &left {
+        let r = match rng.one_in(2) {
+            true => left[rng.below(size)],
+            false => rng.between(10000, 99999),
+        };
+        _ = writeln!(output, "{l}   {r}");
+    }
+    output
+}
+
+// Reports of 5 to 8 levels, safe ones (strictly increasing or decreasing by 1 to 3) with about half
+// of them with a bad level.
+fn reports(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let increasing = rng.one_in(2);
+        let mut level = rng.between(30, 70);
+        let mut levels = vec![];
+        for _ in 0..rng.between(5, 8) {
+            levels.push(level);
+            let step = rng.between(1, 3);
+            level = if increasing {
+                level + step
+            } else {
+                level - step
+            };
+        }
+        if rng.one_in(2) {
+            let i = rng.below(levels.len());
+            levels[i] += rng.between(0, 4);
+        }
+        _ = writeln!(output, "{}", levels.iter().join(" "));
+    }
+    output
+}
+
+// Rules ordering every pair of 49 pages, and updates of 5 to 23 of them, about half of them in the
+// right order.
+fn print_queue(rng: &mut Rng, size: usize) -> String {
+    let mut pages = (10..100).collect_vec();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+    let mut output = String::new();
+    let mut rules = pages.iter().tuple_combinations::<(_, _)>().collect_vec();
+    rng.shuffle(&mut rules);
+    for (before, after) in rules {
+        _ = writeln!(output, "{before}|{after}");
+    }
+    output.push('\n');
+    for _ in 0..size {
+        let mut update = (0..pages.len()).collect_vec();
+        rng.shuffle(&mut update);
+        // Updates always have a middle page.
+        update.truncate(rng.between(2, 11) * 2 + 1);
+        if rng.one_in(2) {
+            update.sort();
+        }
+        _ = writeln!(output, "{}", update.iter().map(|&i| pages[i]).join(","));
+    }
+    output
+}
+
+// Stone numbers of 1 to 7 digits.
+fn stones(rng: &mut Rng, size: usize) -> String {
+    let stones = (0..size).map(|_| {
+        let digits = rng.between(1, 7);
+        rng.below(10usize.pow(digits as u32))
+    });
+    format!("{}\n", stones.format(" "))
+}
+
+// Robots on the default 101x103 room, which make the easter egg picture at some point.
+fn robots(rng: &mut Rng, size: usize) -> String {
+    let seconds = rng.between(1, 101 * 103 - 1);
+    robots_with_egg_at(rng, size, seconds)
+}
+
+// Robots on the default 101x103 room, which make the easter egg picture after the given seconds.
+fn robots_with_egg_at(rng: &mut Rng, size: usize, seconds: usize) -> String {
+    let (width, height) = (101, 103);
+    let (egg_x, egg_y) = (rng.below(width - 31), rng.below(height - 33));
+    let egg = EASTER_EGG.iter().enumerate().flat_map(|(y, line)| {
+        let xs = line.match_indices('x').map(|(x, _)| x);
+        xs.map(move |x| (egg_x + x, egg_y + y))
+    });
+    let mut positions = egg.collect_vec();
+    // The rest of the robots stay out of the picture's frame when it's made.
+    while positions.len() < size {
+        let (x, y) = (rng.below(width), rng.below(height));
+        if !(egg_x..egg_x + 31).contains(&x) || !(egg_y..egg_y + 33).contains(&y) {
+            positions.push((x, y));
+        }
+    }
+    rng.shuffle(&mut positions);
+
+    let mut output = String::new();
+    for (x, y) in positions {
+        let (vx, vy) = (rng.below(width), rng.below(height));
+        // Moves back from the position at the time of the picture.
+        let x0 = (x + (width - vx) * seconds) % width;
+        let y0 = (y + (height - vy) * seconds) % height;
+        // Velocities are the same modulo the room size, so they can be made negative.
+        let vx = if rng.one_in(2) {
+            vx as i64
+        } else {
+            vx as i64 - width as i64
+        };
+        let vy = if rng.one_in(2) {
+            vy as i64
+        } else {
+            vy as i64 - height as i64
+        };
+        _ = writeln!(output, "p={x0},{y0} v={vx},{vy}");
+    }
+    output
+}
+
+// A maze with the start on the bottom left corner and the end on the top right one. It's made of
+// corridors one tile wide, with some loops so that there are several paths to the end. Even sizes
+// are rounded up to odd ones.
+fn maze(rng: &mut Rng, size: usize) -> String {
+    let size = size | 1;
+    let mut maze = vec![vec!['#'; size]; size];
+    // Carves a random spanning tree of the tiles with odd coordinates with a depth-first search.
+    let mut stack = vec![(1usize, size - 2)];
+    maze[size - 2][1] = '.';
+    while let Some(&(x, y)) = stack.last() {
+        let neighbors = [(2, 0), (0, 2), (-2, 0), (0, -2)]
+            .map(|(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .into_iter()
+            .filter(|&(nx, ny)| nx < size && ny < size && maze[ny][nx] == '#')
+            .collect_vec();
+        if neighbors.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = neighbors[rng.below(neighbors.len())];
+        maze[(y + ny) / 2][(x + nx) / 2] = '.';
+        maze[ny][nx] = '.';
+        stack.push((nx, ny));
+    }
+    // Opens some of the walls between corridors.
+    for _ in 0..size * size / 40 {
+        let (x, y) = (rng.between(1, size - 2), rng.between(1, size - 2));
+        if (x + y) % 2 == 1 {
+            maze[y][x] = '.';
+        }
+    }
+    maze[size - 2][1] = 'S';
+    maze[1][size - 2] = 'E';
+    maze.iter()
+        .map(|row| format!("{}\n", String::from_iter(row)))
+        .collect()
+}
+
+// Towels of 1 to 8 stripes, mostly of 3 to 5, and designs of 20 to 60 stripes made from them. No
+// towel is one of the colors alone, or ends with a certain pair of stripes ending with it, so about a
+// quarter of the designs are made impossible by ending them with that pair.
+fn linen_layout(rng: &mut Rng, size: usize) -> String {
+    let mut colors = ['w', 'u', 'b', 'r', 'g'];
+    rng.shuffle(&mut colors);
+    let missing_pair = format!("{}{}", colors[1], colors[0]);
+    let lens = [1, 2, 2, 3, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 6, 6, 7, 8];
+    let mut towels = HashSet::default();
+    while towels.len() < (size + size / 10).clamp(8, 1000) {
+        let len = lens[rng.below(lens.len())];
+        let towel: String = (0..len).map(|_| colors[rng.below(colors.len())]).collect();
+        if towel != colors[0].to_string() && !towel.ends_with(&missing_pair) {
+            towels.insert(towel);
+        }
+    }
+    let mut towels = towels.into_iter().collect_vec();
+    towels.sort();
+    rng.shuffle(&mut towels);
+
+    let mut output = format!("{}\n\n", towels.join(", "));
+    for _ in 0..size {
+        let len = rng.between(20, 60);
+        let mut design = String::new();
+        while design.len() < len {
+            design.push_str(&towels[rng.below(towels.len())]);
+        }
+        if rng.one_in(4) {
+            design.push_str(&missing_pair);
+        }
+        _ = writeln!(output, "{design}");
+    }
+    output
+}
+
+// Initial secret numbers, under 2^24.
+fn secret_numbers(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", rng.between(1, (1 << 24) - 1)))
+        .collect()
+}
+
+// Connections between computers with two letter names, each with 13 connections on average. 13 of
+// them are all connected to each other, which makes the largest set of them.
+fn lan(rng: &mut Rng, size: usize) -> String {
+    let mut names = ('a'..='z').cartesian_product('a'..='z').collect_vec();
+    rng.shuffle(&mut names);
+    names.truncate(size);
+
+    let mut links = HashSet::default();
+    for (a, b) in (0..13).tuple_combinations() {
+        links.insert((a, b));
+    }
+    // Random graphs this sparse don't have more than a few computers all connected to each other.
+    while links.len() < size * 13 / 2 {
+        let (a, b) = (rng.below(size), rng.below(size));
+        if a != b {
+            links.insert((a.min(b), a.max(b)));
+        }
+    }
+    let mut links = links.into_iter().collect_vec();
+    links.sort();
+    rng.shuffle(&mut links);
+    let mut output = String::new();
+    for (a, b) in links {
+        let [(a1, a2), (b1, b2)] = [names[a], names[b]];
+        if rng.one_in(2) {
+            _ = writeln!(output, "{a1}{a2}-{b1}{b2}");
+        } else {
+            _ = writeln!(output, "{b1}{b2}-{a1}{a2}");
+        }
+    }
+    output
+}
+
+#[test]
+fn generators_test() {
+    for generator in year_generators(2024) {
+        let day = aoc::days::find_year(2024)
+            .unwrap()
+            .find(generator.day_num)
+            .unwrap();
+        for size in [*generator.sizes.start(), generator.default_size / 4 + 1] {
+            let input = (generator.generate)(&mut Rng::new(size as u64), size);
+            let day_num = generator.day_num;
+            // Generated inputs have both answers, and all the variants agree on them.
+            let mut all_answers = vec![];
+            for &(variant, solver) in day.variants {
+                let answers = solver(&input, &aoc::Params::default());
+                let answers = answers
+                    .unwrap_or_else(|err| panic!("day {day_num} {variant}, size {size}: {err:#}"));
+                assert!(
+                    answers.part(2).is_some(),
+                    "day {day_num} {variant}, size {size}: no part 2 answer"
+                );
+                all_answers.push(answers.to_string());
+            }
+            assert!(
+                all_answers.iter().all_equal(),
+                "day {day_num}, size {size}: variants disagree: {all_answers:?}"
+            );
+        }
+        // Same seed, same input.
+        let generate = |seed| (generator.generate)(&mut Rng::new(seed), generator.default_size);
+        assert_eq!(generate(1), generate(1));
+    }
+}
+
+#[test]
+fn robots_test() {
+    let input = robots_with_egg_at(&mut Rng::new(7), 500, 1234);
+    let answers =
+        aoc::days::y2024::day_14_restroom_redoubt::run_with_params(&input, &aoc::Params::default());
+    assert_eq!(answers.unwrap().part(2), Some("1234"));
+}
+
+#[test]
+fn lan_test() {
+    let input = lan(&mut Rng::new(7), 100);
+    let answers = aoc::days::y2024::day_23_lan_party::run(&input).unwrap();
+    let password = answers.part(2).unwrap();
+    assert_eq!(password.split(',').count(), 13);
+}
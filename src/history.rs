@@ -0,0 +1,230 @@
+use crate::runner::Job;
+use anyhow::{Context, bail};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const HISTORY_FILE: &str = "timings.csv";
+const HISTORY_HEADER: &str = "date,commit,year,day,variant,elapsed_ms";
+// The header of history files from before there were several years, when all days were of 2024.
+const OLD_HISTORY_HEADER: &str = "date,commit,day,variant,elapsed_ms";
+const OLD_HISTORY_YEAR: &str = "2024";
+
+// How many of the latest timings of a day make up its baseline.
+const BASELINE_RUNS: usize = 5;
+
+pub const DEFAULT_MAX_SLOWDOWN_PCT: f64 = 10.0;
+
+struct Record {
+    year: usize,
+    day_num: usize,
+    variant: String,
+    elapsed: Duration,
+}
+
+// Whether the current directory is the root of the project that the executable was built from,
+// which is where the history file goes. Executables taken elsewhere don't keep a history.
+pub fn in_project_root() -> bool {
+    let canonical_dirs = env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .and_then(|dir| Ok((dir, Path::new(env!("CARGO_MANIFEST_DIR")).canonicalize()?)));
+    canonical_dirs.is_ok_and(|(dir, project_dir)| dir == project_dir)
+}
+
+// Appends the timings of a run to the history file, along with the current date and git commit.
+pub fn append_timings(year: usize, timings: &[(&Job, Duration)]) -> aoc::Result<()> {
+    let (date, commit) = (current_date(), current_commit());
+    let mut contents = String::new();
+    for (job, elapsed) in timings {
+        let (day_num, variant) = (job.day_num, job.variant);
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        contents += &format!("{date},{commit},{year},{day_num},{variant},{elapsed_ms:.3}\n");
+    }
+    let history = match fs::read_to_string(HISTORY_FILE) {
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        res => res.with_context(|| format!("error reading {HISTORY_FILE}"))?,
+    };
+    if history.starts_with(&format!("{HISTORY_HEADER}\n")) {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(HISTORY_FILE)
+            .with_context(|| format!("error opening {HISTORY_FILE}"))?;
+        return file
+            .write_all(contents.as_bytes())
+            .with_context(|| format!("error writing {HISTORY_FILE}"));
+    }
+    // New files get the header, and old ones are rewritten in the current format.
+    let history = migrate_history(&history)?;
+    fs::write(HISTORY_FILE, history + &contents)
+        .with_context(|| format!("error writing {HISTORY_FILE}"))
+}
+
+// Compares the timings of a run with the baseline of each day, which is the median of its latest
+// timings on the history file, and prints them. Fails if any day is more than `max_slowdown_pct`
+// percent slower than its baseline.
+pub fn compare_timings(
+    year: usize,
+    timings: &[(&Job, Duration)],
+    max_slowdown_pct: f64,
+) -> aoc::Result<()> {
+    let history = read_history()?;
+    println!("\nCompared with the median of the last {BASELINE_RUNS} timings:");
+    let mut slower_count = 0;
+    for &(job, elapsed) in timings {
+        let day = format!("Day {}", job.day_num);
+        let Some(baseline) = baseline(&history, year, job.day_num, job.variant) else {
+            println!("{day:<7} {:>10}  no baseline", format!("{elapsed:.2?}"));
+            continue;
+        };
+        let change_pct = (elapsed.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0;
+        let note = if change_pct > max_slowdown_pct {
+            slower_count += 1;
+            "  SLOWER"
+        } else {
+            ""
+        };
+        println!(
+            "{day:<7} {:>10}  baseline {:>10}  {change_pct:>+5.0}%{note}",
+            format!("{elapsed:.2?}"),
+            format!("{baseline:.2?}"),
+        );
+    }
+    if slower_count > 0 {
+        bail!("{slower_count} days are more than {max_slowdown_pct}% slower than their baseline");
+    }
+    Ok(())
+}
+
+fn baseline(history: &[Record], year: usize, day_num: usize, variant: &str) -> Option<Duration> {
+    let mut latest: Vec<_> = history
+        .iter()
+        .rev()
+        .filter(|r| r.year == year && r.day_num == day_num && r.variant == variant)
+        .take(BASELINE_RUNS)
+        .map(|r| r.elapsed)
+        .collect();
+    latest.sort();
+    latest.get(latest.len() / 2).copied()
+}
+
+fn read_history() -> aoc::Result<Vec<Record>> {
+    let contents = match fs::read_to_string(HISTORY_FILE) {
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        res => res.with_context(|| format!("error reading {HISTORY_FILE}"))?,
+    };
+    migrate_history(&contents)?
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<_> = line.split(',').collect();
+            let &[_date, _commit, year, day, variant, elapsed_ms] = &fields[..] else {
+                bail!("invalid line in {HISTORY_FILE}: '{line}'");
+            };
+            let elapsed_ms: f64 = elapsed_ms.parse()?;
+            Ok(Record {
+                year: year.parse()?,
+                day_num: day.parse()?,
+                variant: variant.to_string(),
+                elapsed: Duration::from_secs_f64(elapsed_ms / 1000.0),
+            })
+        })
+        .collect()
+}
+
+// Converts the contents of a history file to the current format, with the current header, and with
+// the year added to the rows of the old format. Files can have rows of both formats, because the
+// current ones used to be appended to old files as is.
+fn migrate_history(contents: &str) -> aoc::Result<String> {
+    let mut lines = contents.lines();
+    match lines.next() {
+        None | Some(HISTORY_HEADER | OLD_HISTORY_HEADER) => {}
+        Some(_) => {
+            bail!("unexpected header on {HISTORY_FILE}; move it away to start a new history")
+        }
+    }
+    let mut migrated = format!("{HISTORY_HEADER}\n");
+    for line in lines {
+        let mut fields: Vec<_> = line.split(',').collect();
+        if fields.len() == 5 {
+            fields.insert(2, OLD_HISTORY_YEAR);
+        }
+        migrated += &format!("{}\n", fields.join(","));
+    }
+    Ok(migrated)
+}
+
+// The current git commit, with a "-dirty" suffix if there are uncommitted changes.
+fn current_commit() -> String {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+fn current_date() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format_utc_date(secs)
+}
+
+// Formats a Unix timestamp as an ISO 8601 UTC date, like "2024-12-25T06:00:00Z". Uses Howard
+// Hinnant's `civil_from_days` algorithm (https://howardhinnant.github.io/date_algorithms.html).
+fn format_utc_date(unix_secs: u64) -> String {
+    let (days, secs) = (unix_secs / 86400, unix_secs % 86400);
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as u64;
+    let (hour, min, sec) = (secs / 3600, secs / 60 % 60, secs % 60);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{min:02}:{sec:02}Z")
+}
+
+#[test]
+fn format_utc_date_test() {
+    assert_eq!(format_utc_date(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_utc_date(951782400), "2000-02-29T00:00:00Z");
+    assert_eq!(format_utc_date(1735106400), "2024-12-25T06:00:00Z");
+}
+
+#[test]
+fn baseline_test() {
+    let record = |day_num, ms| Record {
+        year: 2024,
+        day_num,
+        variant: "default".to_string(),
+        elapsed: Duration::from_millis(ms),
+    };
+    let history = [1, 50, 2, 3, 4, 5, 6].map(|ms| record(1, ms));
+    assert_eq!(
+        baseline(&history, 2024, 1, "default"),
+        Some(Duration::from_millis(4))
+    );
+    assert_eq!(baseline(&history, 2024, 1, "other"), None);
+    assert_eq!(baseline(&history, 2024, 2, "default"), None);
+    assert_eq!(baseline(&history, 2025, 1, "default"), None);
+}
+
+#[test]
+fn migrate_history_test() {
+    let old = "date,commit,day,variant,elapsed_ms\nd,c,1,default,1.5\nd,c,2024,2,default,2.5\n";
+    assert_eq!(
+        migrate_history(old).unwrap(),
+        "date,commit,year,day,variant,elapsed_ms\nd,c,2024,1,default,1.5\nd,c,2024,2,default,2.5\n"
+    );
+    assert_eq!(migrate_history("").unwrap(), format!("{HISTORY_HEADER}\n"));
+    assert!(migrate_history("something,else\n").is_err());
+}
@@ -1,21 +1,346 @@
+// Lets the day modules refer to this crate as `aoc`, the same as any other user of it.
+extern crate self as aoc;
+
+pub mod days;
+
+use anyhow::{Context, anyhow};
 use itertools::Itertools;
-use std::{fmt::Display, result, str::FromStr};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use std::{result, str::FromStr};
 
 pub type Result<T> = anyhow::Result<T>;
-pub type Answer = Result<String>;
+pub type Answer = Result<Answers>;
+
+/// The answers for the two parts of a puzzle, already formatted for display. Only day 25 has no
+/// part 2 answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn part(&self, part_num: usize) -> Option<&str> {
+        match part_num {
+            1 => Some(&self.part1),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.part2 {
+            Some(part2) => write!(f, "{} {part2}", self.part1),
+            None => write!(f, "{}", self.part1),
+        }
+    }
+}
+
+// Allows comparing answers with their displayed form, e.g. `assert_eq!(run(sample)?, "11 31")`.
+impl PartialEq<&str> for Answers {
+    fn eq(&self, other: &&str) -> bool {
+        let Some(rest) = other.strip_prefix(self.part1.as_str()) else {
+            return false;
+        };
+        match &self.part2 {
+            Some(part2) => rest.strip_prefix(' ') == Some(part2.as_str()),
+            None => rest.is_empty(),
+        }
+    }
+}
 
 pub fn answers(p1: impl Display, p2: impl Display) -> Answer {
-    Ok(format!("{p1} {p2}"))
+    Ok(Answers {
+        part1: p1.to_string(),
+        part2: Some(p2.to_string()),
+    })
 }
 
+/// The answer of a part that was skipped on purpose, like because of the parameters of the day.
+/// It's an answer of its own, so a skipped part can be told apart from a missing one.
+pub const SKIPPED: &str = "skipped";
+
 pub fn answer(ans: impl Display) -> Answer {
-    Ok(format!("{ans}"))
+    Ok(Answers {
+        part1: ans.to_string(),
+        part2: None,
+    })
+}
+
+/// Named parameters for running a day on inputs other than an actual puzzle input, e.g. samples
+/// with a smaller grid. Days that take parameters have a `run_with_params()` function, which gets
+/// them with defaults for the actual puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str, default: T) -> Result<T>
+    where
+        T::Err: Display,
+    {
+        match self.0.iter().find(|(n, _)| n == name) {
+            Some((_, value)) => value
+                .parse()
+                .map_err(|err| anyhow!("invalid {name} parameter '{value}': {err}")),
+            None => Ok(default),
+        }
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let params = self.0.iter().map(|(name, value)| format!("{name}={value}"));
+        write!(f, "{}", params.format(" "))
+    }
 }
 
-pub fn parse_numbers<T: FromStr>(s: &str) -> result::Result<Vec<T>, T::Err> {
+// Parses parameters like "width=11 height=7".
+impl FromStr for Params {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Params> {
+        let mut params = vec![];
+        for param in s.split_whitespace() {
+            let (name, value) = param
+                .split_once('=')
+                .with_context(|| format!("invalid parameter '{param}'; expected NAME=VALUE"))?;
+            params.push((name.to_string(), value.to_string()));
+        }
+        Ok(Params(params))
+    }
+}
+
+/// An error about a specific part of a puzzle input. Days parse their inputs by slicing them, so that
+/// part is identified by the address of its slice. That way, days don't need to keep track of line
+/// numbers; the error gets located when the whole input is at hand, with `locate_error()`.
+#[derive(Debug)]
+pub struct InputError {
+    message: String,
+    address: usize,
+    len: usize,
+}
+
+impl InputError {
+    pub fn new(at: &str, message: impl Display) -> InputError {
+        InputError {
+            message: message.to_string(),
+            address: at.as_ptr() as usize,
+            len: at.len(),
+        }
+    }
+
+    // Returns the byte range of the offending part on the given input, if it's a slice of it.
+    fn range_in(&self, input: &str) -> Option<Range<usize>> {
+        let start = self.address.checked_sub(input.as_ptr() as usize)?;
+        (start + self.len <= input.len()).then_some(start..start + self.len)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn input_error(at: &str, message: impl Display) -> anyhow::Error {
+    InputError::new(at, message).into()
+}
+
+/// Like anyhow's `Context`, but the error is an `InputError` about the given part of the input.
+pub trait InputContext<T> {
+    fn context_at(self, at: &str, message: impl Display) -> Result<T>;
+}
+
+impl<T> InputContext<T> for Option<T> {
+    fn context_at(self, at: &str, message: impl Display) -> Result<T> {
+        self.ok_or_else(|| input_error(at, message))
+    }
+}
+
+impl<T, E: Into<anyhow::Error>> InputContext<T> for result::Result<T, E> {
+    fn context_at(self, at: &str, message: impl Display) -> Result<T> {
+        self.map_err(|err| err.into().context(InputError::new(at, message)))
+    }
+}
+
+/// Adds the location of an `InputError` on the given input to the error message, like
+/// "inputs/2024/05.txt:1177:3: expected two numbers", followed by the offending line and a caret
+/// pointing at the error. Other errors are returned as they are.
+pub fn locate_error(err: anyhow::Error, input: &str, input_name: &str) -> anyhow::Error {
+    let Some(range) = err
+        .downcast_ref::<InputError>()
+        .and_then(|input_err| input_err.range_in(input))
+    else {
+        return err;
+    };
+    let line_start = input[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[range.start..]
+        .find('\n')
+        .map_or(input.len(), |i| range.start + i);
+    let line = &input[line_start..line_end];
+    let line_num = input[..range.start].matches('\n').count() + 1;
+    let column = input[line_start..range.start].chars().count() + 1;
+    let caret_len = input[range.start..range.end.min(line_end)].chars().count();
+
+    let gutter = " ".repeat(line_num.to_string().len());
+    let padding = " ".repeat(column - 1);
+    let carets = "^".repeat(caret_len.max(1));
+    anyhow!(
+        "{input_name}:{line_num}:{column}: {err:#}\n{line_num} | {line}\n{gutter} | {padding}{carets}"
+    )
+}
+
+/// A timed phase of a day's solution, as recorded by `trace()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub name: &'static str,
+    /// How many spans this one is nested in.
+    pub depth: usize,
+    /// When the span started, relative to the start of the trace.
+    pub start: Duration,
+    pub elapsed: Duration,
+}
+
+struct Tracer {
+    origin: Instant,
+    depth: usize,
+    spans: Vec<Span>,
+}
+
+thread_local! {
+    static TRACER: RefCell<Option<Tracer>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` as a named span, like "parse" or "part 2", so that its time shows up separately when the
+/// day is traced. Spans started inside `f` are nested in this one. When not tracing, it just runs `f`.
+pub fn span<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let index = TRACER.with_borrow_mut(|tracer| {
+        let tracer = tracer.as_mut()?;
+        let start = tracer.origin.elapsed();
+        let depth = tracer.depth;
+        tracer.spans.push(Span {
+            name,
+            depth,
+            start,
+            elapsed: Duration::ZERO,
+        });
+        tracer.depth += 1;
+        Some(tracer.spans.len() - 1)
+    });
+    let Some(index) = index else {
+        return f();
+    };
+    let result = f();
+    TRACER.with_borrow_mut(|tracer| {
+        if let Some(tracer) = tracer {
+            let now = tracer.origin.elapsed();
+            let span = &mut tracer.spans[index];
+            span.elapsed = now - span.start;
+            tracer.depth -= 1;
+        }
+    });
+    result
+}
+
+/// Runs `f` while recording the spans started on the current thread, and returns them in the order
+/// they started.
+pub fn trace<T>(f: impl FnOnce() -> T) -> (T, Vec<Span>) {
+    // Stops tracing even if `f` panics.
+    struct StopTracing;
+    impl Drop for StopTracing {
+        fn drop(&mut self) {
+            TRACER.set(None);
+        }
+    }
+    let _stop_tracing = StopTracing;
+    TRACER.set(Some(Tracer {
+        origin: Instant::now(),
+        depth: 0,
+        spans: vec![],
+    }));
+    let result = f();
+    let spans = TRACER.take().map_or(vec![], |tracer| tracer.spans);
+    (result, spans)
+}
+
+/// Fixes the oddities that inputs get from being edited or copy-pasted, which would otherwise trip the
+/// days up: a byte order mark at the start is removed, and Windows line endings (`\r\n`) are
+/// converted to `\n`. Returns the normalized input along with errors about those oddities, and also
+/// about the ones that are not fixed: trailing whitespace and empty lines at the end.
+pub fn normalize_input(input: &str) -> (Cow<'_, str>, Vec<InputError>) {
+    let mut normalized = Cow::Borrowed(input);
+    let mut issues = vec![];
+    if let Some(rest) = input.strip_prefix('\u{feff}') {
+        issues.push(InputError::new(&input[..3], "byte order mark"));
+        normalized = Cow::Borrowed(rest);
+    }
+
+    let mut crlfs = input.match_indices("\r\n").map(|(i, _)| &input[i..i + 1]);
+    if let Some(first) = crlfs.next() {
+        let message = and_others("Windows line ending (\\r\\n)", crlfs.count());
+        issues.push(InputError::new(first, message));
+        normalized = Cow::Owned(normalized.replace("\r\n", "\n"));
+    }
+
+    let mut trailing_spaces = input.lines().filter_map(|line| {
+        let trimmed_len = line.trim_end_matches([' ', '\t']).len();
+        (trimmed_len < line.len()).then(|| &line[trimmed_len..])
+    });
+    if let Some(first) = trailing_spaces.next() {
+        let message = and_others("trailing whitespace", trailing_spaces.count());
+        issues.push(InputError::new(first, message));
+    }
+
+    let end = &input[input.trim_end_matches(['\r', '\n']).len()..];
+    if let Some((_, empty_lines)) = end.split_once('\n')
+        && empty_lines.contains('\n')
+    {
+        issues.push(InputError::new(empty_lines, "empty lines at the end"));
+    }
+    (normalized, issues)
+}
+
+fn and_others(message: &str, other_count: usize) -> String {
+    match other_count {
+        0 => message.to_string(),
+        1 => format!("{message}, here and on 1 other line"),
+        n => format!("{message}, here and on {n} other lines"),
+    }
+}
+
+/// Splits an input made of two sections separated by an empty line.
+pub fn split_sections(input: &str) -> Result<(&str, &str)> {
+    let end = &input[input.len()..];
+    input
+        .split_once("\n\n")
+        .context_at(end, "section separator not found")
+}
+
+/// Splits an input made of any number of sections separated by empty lines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input.trim_end_matches('\n').split("\n\n")
+}
+
+/// Parses all the numbers in the given string, ignoring anything between them.
+pub fn parse_numbers<T: FromStr>(s: &str) -> Result<Vec<T>>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
     s.split(|ch: char| !ch.is_ascii_digit() && ch != '-')
         .filter(|s| !s.is_empty())
-        .map(str::parse)
+        .map(|n| n.parse().context_at(n, "invalid number"))
         .collect()
 }
 
@@ -27,15 +352,22 @@ pub fn parse_grid<T>(
     input: &str,
     parse_char: impl Fn(char) -> Result<T>,
 ) -> Result<(Vec<Vec<T>>, usize, usize)> {
-    let grid: Vec<Vec<_>> = input
-        .lines()
-        .map(|l| l.chars().map(&parse_char).try_collect())
-        .try_collect()?;
+    let parse_line = |line: &str| {
+        line.char_indices()
+            .map(|(i, ch)| {
+                let at = &line[i..i + ch.len_utf8()];
+                parse_char(ch).map_err(|err| input_error(at, format!("{err:#}")))
+            })
+            .try_collect()
+    };
+    let grid: Vec<Vec<_>> = span("parse_grid", || input.lines().map(parse_line).try_collect())?;
 
     let height = grid.len();
     let width = grid.first().map_or(0, |r| r.len());
-    for row in grid.iter() {
-        anyhow::ensure!(row.len() == width, "rows must be all the same length");
+    for (row, line) in grid.iter().zip(input.lines()) {
+        if row.len() != width {
+            return Err(input_error(line, "rows must be all the same length"));
+        }
     }
 
     Ok((grid, width, height))
@@ -49,3 +381,106 @@ pub fn grid_to_str(grid: &[Vec<char>]) -> String {
     }
     s
 }
+
+#[test]
+fn params_test() {
+    let params: Params = "width=11 height=7".parse().unwrap();
+    assert_eq!(params.get("width", 101).unwrap(), 11);
+    assert_eq!(params.get("height", 103).unwrap(), 7);
+    assert_eq!(params.get("seconds", 100).unwrap(), 100);
+    assert!(params.get("width", false).is_err());
+    assert!("".parse::<Params>().unwrap().is_empty());
+    assert!("width".parse::<Params>().is_err());
+    assert_eq!(params.to_string(), "width=11 height=7");
+}
+
+#[test]
+fn locate_error_test() {
+    let input = "1,2\n3,x400\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = parse_numbers::<u8>(line)
+        .unwrap_err()
+        .context("invalid line");
+    assert_eq!(
+        locate_error(err, input, "in.txt").to_string(),
+        "in.txt:2:4: invalid line: invalid number: number too large to fit in target type\n\
+        2 | 3,x400\n  |    ^^^"
+    );
+
+    let err = input_error(&input[input.len()..], "unexpected end");
+    assert_eq!(
+        locate_error(err, input, "in.txt").to_string(),
+        "in.txt:3:1: unexpected end\n3 | \n  | ^"
+    );
+
+    // Errors about other strings are not located.
+    let err = parse_numbers::<u8>("300").unwrap_err();
+    assert_eq!(
+        locate_error(err, input, "in.txt").to_string(),
+        "invalid number"
+    );
+}
+
+#[test]
+fn normalize_input_test() {
+    let issues = |input| {
+        let (normalized, issues) = normalize_input(input);
+        let issues = issues.into_iter().map(|issue| {
+            let located = locate_error(issue.into(), input, "in.txt").to_string();
+            located.lines().next().unwrap().to_string()
+        });
+        (normalized.into_owned(), issues.collect_vec())
+    };
+    assert_eq!(issues("1 2\n3 4\n"), ("1 2\n3 4\n".to_string(), vec![]));
+    assert_eq!(issues("1 2\n3 4"), ("1 2\n3 4".to_string(), vec![]));
+    assert_eq!(
+        issues("\u{feff}1 2\r\n\r\n3 4\r\n"),
+        (
+            "1 2\n\n3 4\n".to_string(),
+            vec![
+                "in.txt:1:1: byte order mark".to_string(),
+                "in.txt:1:5: Windows line ending (\\r\\n), here and on 2 other lines".to_string(),
+            ]
+        )
+    );
+    assert_eq!(
+        issues("1 2 \n3 4\t\n\n\n"),
+        (
+            "1 2 \n3 4\t\n\n\n".to_string(),
+            vec![
+                "in.txt:1:4: trailing whitespace, here and on 1 other line".to_string(),
+                "in.txt:3:1: empty lines at the end".to_string(),
+            ]
+        )
+    );
+}
+
+#[test]
+fn sections_test() {
+    assert_eq!(
+        sections("a\nb\n\nc\n\nd\n").collect::<Vec<_>>(),
+        ["a\nb", "c", "d"]
+    );
+    assert_eq!(sections("a\n").collect::<Vec<_>>(), ["a"]);
+    let input = "1 2\n\n3\n";
+    let section = sections(input).nth(1).unwrap();
+    assert_eq!(
+        locate_error(input_error(section, "bad section"), input, "in.txt").to_string(),
+        "in.txt:3:1: bad section\n3 | 3\n  | ^"
+    );
+}
+
+#[test]
+fn trace_test() {
+    let (sum, spans) = trace(|| {
+        let a = span("a", || span("a1", || 1) + span("a2", || 2));
+        a + span("b", || 3)
+    });
+    assert_eq!(sum, 6);
+    let spans = spans.iter().map(|s| (s.name, s.depth)).collect_vec();
+    assert_eq!(spans, [("a", 0), ("a1", 1), ("a2", 1), ("b", 0)]);
+
+    // Spans outside of a trace are not recorded.
+    assert_eq!(span("c", || 4), 4);
+    assert_eq!(trace(|| ()).1, []);
+}
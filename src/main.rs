@@ -1,102 +1,662 @@
 use anyhow::{Context, bail, ensure};
-use std::{env, fs, io::IsTerminal, thread, time};
-
-mod day_01_historian_hysteria;
-mod day_02_red_nosed_reports;
-mod day_03_mull_it_over;
-mod day_04_ceres_search;
-mod day_05_print_queue;
-mod day_06_guard_gallivant;
-mod day_07_bridge_repair;
-mod day_08_resonant_collinearity;
-mod day_09_disk_fragmenter;
-mod day_10_hoof_it;
-mod day_11_plutonian_pebbles;
-mod day_12_garden_groups;
-mod day_13_claw_contraption;
-mod day_14_restroom_redoubt;
-mod day_15_warehouse_woes;
-mod day_16_reindeer_maze;
-mod day_16_reindeer_maze_custom_dijkstra;
-mod day_17_chronospatial_computer;
-mod day_18_ram_run;
-mod day_19_linen_layout;
-mod day_20_race_condition;
-mod day_21_keypad_conundrum;
-mod day_22_monkey_market;
-mod day_23_lan_party;
-mod day_24_crossed_wires;
-mod day_25_code_chronicle;
-
-const DAYS: [fn(&str) -> aoc::Result<String>; 25] = [
-    day_01_historian_hysteria::run,
-    day_02_red_nosed_reports::run,
-    day_03_mull_it_over::run,
-    day_04_ceres_search::run,
-    day_05_print_queue::run,
-    day_06_guard_gallivant::run,
-    day_07_bridge_repair::run,
-    day_08_resonant_collinearity::run,
-    day_09_disk_fragmenter::run,
-    day_10_hoof_it::run,
-    day_11_plutonian_pebbles::run,
-    day_12_garden_groups::run,
-    day_13_claw_contraption::run,
-    day_14_restroom_redoubt::run,
-    day_15_warehouse_woes::run,
-    day_16_reindeer_maze::run,
-    day_17_chronospatial_computer::run,
-    day_18_ram_run::run,
-    day_19_linen_layout::run,
-    day_20_race_condition::run,
-    day_21_keypad_conundrum::run,
-    day_22_monkey_market::run,
-    day_23_lan_party::run,
-    day_24_crossed_wires::run,
-    day_25_code_chronicle::run,
-];
+use aoc::days;
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::{env, thread, time};
+
+mod bench;
+mod check;
+mod dashboard;
+mod embedded;
+mod fuzz;
+mod generators;
+mod history;
+mod mem;
+mod multi_input;
+mod output;
+mod rng;
+mod runner;
+mod samples;
+mod scaffold;
+mod server;
+mod trace;
+
+const USAGE: &str = "usage: aoc [OPTIONS] [YEAR] [DAYS]... | aoc new [YEAR] DAY TITLE | aoc serve \
+    [--port PORT] | aoc generate [YEAR] DAY [--size N] [--seed N] (see --help for more information)";
+
+const HELP: &str =
+    "Runs the Advent of Code solutions for the given year and days, or all days if none are given.
+
+Usage: aoc [OPTIONS] [YEAR] [DAYS]...
+       aoc new [YEAR] DAY TITLE
+       aoc serve [--port PORT] [--timeout SECS] [--strict-input]
+       aoc generate [YEAR] DAY [--size N] [--seed N]
+
+YEAR defaults to the latest year with solutions. It can also be given with --year.
+
+The \"new\" command creates the source file for a new day from src/day_template.rs, registers it in
+the year's registry on src/days/yYEAR.rs (which gets created for a new year), and creates an empty
+input file for it. It must be run from the project root.
+
+The \"serve\" command serves an HTTP API on localhost, port 2024 by default. POST /days/N runs day N
+on the request body, with optional part, variant and year query parameters that work like the
+--part, --variant and --year options, and responds with a JSON object with the answers, run time
+and error.
+
+The \"generate\" command writes a random input for the day to stdout, which is well-formed and has
+answers. Its size is the number of lines, robots, etc, depending on the day [default: about the size
+of real inputs], and it's always the same for the same --seed [default: based on the current time].
+Only some days have a generator; \"aoc generate --list\" shows which ones, and what their sizes
+count.
+
+DAYS can be day numbers (17), ranges (5-10), comma-separated lists of those (1,3,17 or 1-5,20), or
+\"odd\", \"even\" or \"all\". Days always run in ascending order.
+
+Options:
+  --year YEAR              Run the days of the given year [default: the latest year]
+  --except DAYS            Skip the given days
+  --input PATH             Read the input from PATH (\"-\" for stdin). Requires a single day
+  --input-dir DIR          Read YEAR/NN.txt input files from DIR [default: inputs, or
+                           $AOC_INPUT_DIR]
+  --inputs-dir DIR         Run the days on every NN-<name>.txt input file in DIR, and check them
+                           against the NN-<name>.answer files next to them
+  --strict-input           Fail on inputs with a byte order mark, Windows line endings, trailing
+                           whitespace or empty lines at the end, instead of warning about them
+  --part 1|2               Only show the answer of the given part
+  --format text|json|csv   Output format [default: text]
+  --live                   Show a table of all days with their status, run time and answers,
+                           updated as they run. Only on a terminal; same as text format otherwise
+  --variant NAME           Run the NAME alternative solution of the given days, or of all the days
+                           that have one
+  --cross-check            Run all variants of the given days and check that their answers match
+  --list                   List the available days and their variants
+  --samples                Run the puzzle samples in samples/YEAR and check their answers
+  --check                  Check the answers against answers/YEAR.txt
+  --update-answers         Rewrite answers/YEAR.txt with the current answers
+  --bench                  Run each day several times and show timing statistics
+  --runs N                 How many times --bench runs each day [default: 10], or how many inputs
+                           --fuzz tries on each day [default: 1000]
+  --mem                    Show the number of allocations, bytes allocated and peak memory usage of
+                           each day, along with its run time
+  --trace                  Show how the run time of each day splits into the spans of its phases
+  --trace-file FILE        Like --trace, and also write the spans to FILE as a Chrome trace
+  --fuzz                   Run each day on random inputs and report the ones that make it panic,
+                           overflow its stack or hang (for longer than --timeout, or 1 second),
+                           with a minimized reproducer for each
+  --seed N                 The random seed for --fuzz and generate [default: based on the current
+                           time]
+  --compare                Fail if any day is more than 10% slower than the median of its latest
+                           timings on timings.csv
+  --compare-threshold PCT  Like --compare, with PCT percent instead of 10%
+  --day-budget [DAY=]MS    Fail --bench if a day's median time is over MS milliseconds
+  --total-budget MS        Fail --bench if the sum of median times is over MS [default: 1000]
+  --timeout SECS           Report days that take longer than SECS seconds as failed
+  --jobs N                 Run up to N days in parallel [default: number of CPUs, or 1 when
+                           showing timings so they are not affected by other days]
+  --sequential             Run one day at a time; same as --jobs 1
+  -h, --help               Show this help";
+
+#[derive(Default)]
+struct Options {
+    year: Option<usize>,
+    days: BTreeSet<usize>,
+    except: BTreeSet<usize>,
+    input: Option<String>,
+    input_dir: Option<String>,
+    inputs_dir: Option<String>,
+    check: bool,
+    update_answers: bool,
+    part: Option<usize>,
+    bench: bool,
+    runs: Option<usize>,
+    strict_input: bool,
+    live: bool,
+    mem: bool,
+    trace: bool,
+    trace_file: Option<String>,
+    fuzz: bool,
+    fuzz_worker: bool,
+    seed: Option<u64>,
+    compare: Option<f64>,
+    day_budgets: Vec<(Option<usize>, u64)>,
+    total_budget: Option<u64>,
+    timeout: Option<time::Duration>,
+    jobs: Option<usize>,
+    variant: Option<String>,
+    cross_check: bool,
+    samples: bool,
+    format: output::Format,
+    list: bool,
+    help: bool,
+    new_day: Option<(usize, String)>,
+    serve: bool,
+    generate: bool,
+    size: Option<usize>,
+    port: Option<u16>,
+}
 
 fn main() -> aoc::Result<()> {
-    let args: Vec<_> = env::args().collect();
-    match args.len() {
-        1 => {
-            let handles: Vec<_> = (1..=DAYS.len())
-                .map(|n| thread::spawn(move || run_single_day(n)))
-                .collect();
-            for handle in handles {
-                let output = handle.join().unwrap_or_else(|_| bail!("thread panicked"))?;
-                println!("{output}")
-            }
+    let options = parse_options(env::args().skip(1))?;
+    if options.help {
+        println!("{HELP}");
+        return Ok(());
+    }
+    if let Some((day_num, title)) = &options.new_day {
+        let year = options.year.unwrap_or(days::latest_year().number);
+        return scaffold::new_day(year, *day_num, title, &input_dir(&options));
+    }
+    let year = match options.year {
+        Some(year) => {
+            days::find_year(year).with_context(|| format!("no solutions for year {year}"))?
         }
-        2 => {
-            let n = args[1].parse().context("invalid day number")?;
-            ensure!(1 <= n && n <= DAYS.len(), "day number out of range");
-            let output = run_single_day(n)?;
-            println!("{output}");
+        None => days::latest_year(),
+    };
+    if options.generate {
+        return generate_input(year.number, &options);
+    }
+    if options.list {
+        for day in year.days {
+            let variants = day.variants.iter().map(|(name, _)| name).join(", ");
+            println!("Day {}: {} ({variants})", day.number, day.title);
         }
-        _ => {
-            bail!("usage: {} [day_number]", args[0]);
+        return Ok(());
+    }
+    let input_dir = input_dir(&options);
+    if options.serve {
+        runner::install_panic_hook();
+        let settings = server::Settings {
+            timeout: options.timeout,
+            strict_input: options.strict_input,
+        };
+        return server::serve(options.port.unwrap_or(server::DEFAULT_PORT), settings);
+    }
+    ensure!(
+        options.part.is_none() || !options.update_answers,
+        "--update-answers cannot be used with --part"
+    );
+
+    let selected_days = if !options.days.is_empty() {
+        options.days
+    } else if let Some(variant) = &options.variant {
+        // Only some days have alternative solutions, so a variant selects the days that have it.
+        let days = year
+            .days
+            .iter()
+            .filter(|day| day.variant(Some(variant)).is_ok());
+        let day_nums: BTreeSet<_> = days.map(|day| day.number).collect();
+        ensure!(!day_nums.is_empty(), "no day has a '{variant}' variant");
+        day_nums
+    } else {
+        year.days.iter().map(|day| day.number).collect()
+    };
+    let day_nums = selected_days
+        .difference(&options.except)
+        .copied()
+        .collect_vec();
+    ensure!(!day_nums.is_empty(), "no days selected");
+    ensure!(
+        options.input.is_none() || day_nums.len() == 1,
+        "--input requires a single day"
+    );
+    let input_path = |day_num| {
+        let input_path = options.input.clone();
+        input_path.unwrap_or_else(|| default_input_path(&input_dir, year.number, day_num))
+    };
+    // An explicit --input is always read, but inputs on the input dir may be missing if they are
+    // embedded.
+    let embedded_input = |day_num| {
+        let embedded = embedded::input(year.number, day_num);
+        embedded.filter(|_| options.input.is_none())
+    };
+    let checking =
+        options.check || options.update_answers || options.cross_check || options.samples;
+    let workers = options.jobs.unwrap_or_else(|| {
+        if !checking && (output::shows_timings(options.format) || options.compare.is_some()) {
+            1
+        } else {
+            thread::available_parallelism().map_or(1, usize::from)
         }
+    });
+    // Only timings of sequential runs on the actual puzzle inputs are comparable with each other,
+    // and they are only recorded on the project they belong to.
+    let records_history = options.input.is_none()
+        && input_dir == DEFAULT_INPUT_DIR
+        && workers == 1
+        && history::in_project_root();
+    ensure!(
+        options.compare.is_none() || records_history,
+        "--compare can only be used from the project root, on the default inputs, running one day \
+        at a time"
+    );
+    runner::install_panic_hook();
+
+    if options.cross_check {
+        // Only days with alternative solutions are worth cross-checking.
+        let days = day_nums.iter().filter_map(|&n| year.find(n));
+        let jobs = days
+            .filter(|day| day.variants.len() > 1)
+            .flat_map(|day| {
+                let variants = day.variants.iter();
+                variants.map(|&(name, solver)| {
+                    let mut job =
+                        runner::Job::new(day.number, name, solver, input_path(day.number));
+                    job.embedded_input = embedded_input(day.number);
+                    job.strict_input = options.strict_input;
+                    job
+                })
+            })
+            .collect_vec();
+        ensure!(
+            !jobs.is_empty(),
+            "none of the selected days has alternative solutions"
+        );
+        let results = runner::run_days(&jobs, workers, options.timeout);
+        return check::cross_check_variants(&jobs, results);
+    }
+
+    let day_solver = |day_num| {
+        let day = year.find(day_num).context("day not found")?;
+        day.variant(options.variant.as_deref())
+    };
+    let make_job = |day_num, input_path| {
+        let (variant, solver) = day_solver(day_num)?;
+        let mut job = runner::Job::new(day_num, variant, solver, input_path);
+        job.strict_input = options.strict_input;
+        aoc::Result::Ok(job)
+    };
+
+    if options.fuzz_worker {
+        ensure!(day_nums.len() == 1, "--fuzz-worker requires a single day");
+        let (_, solver) = day_solver(day_nums[0])?;
+        return fuzz::run_worker(solver);
+    }
+
+    if options.fuzz {
+        let runs = options.runs.unwrap_or(fuzz::DEFAULT_RUNS);
+        let seed = options.seed.unwrap_or_else(rng::random_seed);
+        let settings = fuzz::Settings {
+            year: year.number,
+            variant: options.variant.clone(),
+            runs,
+            seed,
+            timeout: options.timeout.unwrap_or(fuzz::DEFAULT_TIMEOUT),
+        };
+        return fuzz::fuzz_days(&day_nums, &settings);
+    }
+
+    if options.samples {
+        let samples = samples::load_samples(&samples::samples_dir(year.number), &day_nums)?;
+        let jobs: Vec<_> = samples
+            .iter()
+            .map(|sample| {
+                let (variant, solver) = day_solver(sample.day_num)?;
+                let mut job = sample.job(variant, solver);
+                job.strict_input = options.strict_input;
+                aoc::Result::Ok(job)
+            })
+            .try_collect()?;
+        let results = runner::run_days(&jobs, workers, options.timeout);
+        return samples::check_samples(&samples, results, options.part);
+    }
+
+    if let Some(inputs_dir) = &options.inputs_dir {
+        let inputs = multi_input::find_inputs(inputs_dir, &day_nums)?;
+        let jobs: Vec<_> = inputs
+            .iter()
+            .map(|input| make_job(input.day_num, input.path.clone()))
+            .try_collect()?;
+        let results = runner::run_days(&jobs, workers, options.timeout);
+        return multi_input::check_inputs(&inputs, results, options.part);
+    }
+
+    let jobs: Vec<_> = day_nums
+        .iter()
+        .map(|&day_num| {
+            let mut job = make_job(day_num, input_path(day_num))?;
+            job.embedded_input = embedded_input(day_num);
+            aoc::Result::Ok(job)
+        })
+        .try_collect()?;
+
+    if options.mem {
+        return mem::run_mem_profile(&jobs);
+    }
+
+    if options.trace {
+        return trace::run_traced(&jobs, options.trace_file.as_deref());
     }
 
+    if options.bench {
+        let runs = options.runs.unwrap_or(bench::DEFAULT_RUNS);
+        let budgets = bench::Budgets::new(&options.day_budgets, options.total_budget);
+        return bench::run_benchmarks(&jobs, runs, &budgets);
+    }
+
+    if options.check || options.update_answers {
+        let results = runner::run_days(&jobs, workers, options.timeout);
+        let answers = day_nums
+            .iter()
+            .copied()
+            .zip(results.map(|res| res.map(|(answer, _)| answer)))
+            .collect_vec();
+        let update = options.update_answers;
+        return check::check_answers(year.number, &answers, options.part, update);
+    }
+
+    let mut timings = vec![];
+    let fail_count = if options.live && dashboard::is_available(options.format) {
+        let results = dashboard::run_dashboard(&jobs, workers, options.timeout, options.part);
+        for (job, res) in jobs.iter().zip(&results) {
+            if let Ok((_, elapsed)) = res {
+                timings.push((job, *elapsed));
+            }
+        }
+        results.iter().filter(|res| res.is_err()).count()
+    } else {
+        let results = runner::run_days(&jobs, workers, options.timeout);
+        let records = jobs.iter().zip(results).inspect(|(job, res)| {
+            if let Ok((_, elapsed)) = res {
+                timings.push((*job, *elapsed));
+            }
+        });
+        let records = records.map(|(job, res)| (job.day_num, job.input_path.clone(), res));
+        output::print_results(options.format, options.part, records)
+    };
+
+    let mut comparison = None;
+    if records_history {
+        // Compare before appending, so the current timings are not part of their own baseline.
+        comparison = options
+            .compare
+            .map(|pct| history::compare_timings(year.number, &timings, pct));
+        // The history is a side effect, which doesn't make the run fail.
+        if let Err(err) = history::append_timings(year.number, &timings) {
+            eprintln!("warning: {err:#}");
+        }
+    }
+    ensure!(
+        fail_count == 0,
+        "{fail_count} of {} days failed",
+        day_nums.len()
+    );
+    comparison.unwrap_or(Ok(()))
+}
+
+// Writes a random input for a day to stdout, or the list of days with input generators.
+fn generate_input(year: usize, options: &Options) -> aoc::Result<()> {
+    let generators = generators::year_generators(year);
+    let day_count = days::find_year(year).map_or(0, |y| y.days.len());
+    let scope = format!("{} of the {day_count} days have one", generators.len());
+    if options.list {
+        println!("Input generators ({scope}):");
+        for generator in generators {
+            let day_num = generator.day_num;
+            let (size_of, size) = (generator.size_of, generator.default_size);
+            println!("Day {day_num}: size is {size_of} [default: {size}]");
+        }
+        return Ok(());
+    }
+    let day_num = options.days.iter().exactly_one().ok();
+    let day_num = *day_num.context("generate requires a single day")?;
+    let generator = generators.iter().find(|g| g.day_num == day_num);
+    let generator = generator.with_context(|| {
+        let days = generators.iter().map(|g| g.day_num).join(", ");
+        format!("day {day_num} has no input generator ({scope}: {days})")
+    })?;
+    let size = options.size.unwrap_or(generator.default_size);
+    let sizes = &generator.sizes;
+    ensure!(
+        sizes.contains(&size),
+        "size for day {day_num} must be between {} and {}",
+        sizes.start(),
+        sizes.end()
+    );
+    let seed = options.seed.unwrap_or_else(rng::random_seed);
+    if options.seed.is_none() {
+        eprintln!("Generating with seed {seed} (use --seed {seed} to repeat)");
+    }
+    print!("{}", (generator.generate)(&mut rng::Rng::new(seed), size));
     Ok(())
 }
 
-fn run_single_day(day_num: usize) -> aoc::Result<String> {
-    let instant = time::Instant::now();
-    let filename = format!("inputs/{day_num:02}.txt");
-    let input =
-        fs::read_to_string(&filename).with_context(|| format!("error reading {filename}"))?;
-    let output = DAYS[day_num - 1](&input)?;
-    let time_annotation = format_time_annotation(instant.elapsed());
-    Ok(format!("Day {day_num}{time_annotation}: {output}"))
+fn parse_options(args: impl Iterator<Item = String>) -> aoc::Result<Options> {
+    let mut args = args.peekable();
+    let mut options = Options::default();
+    if args.next_if_eq("new").is_some() {
+        if let Some(year) = args.next_if(|arg| is_year(arg)) {
+            options.year = Some(year.parse()?);
+        }
+        let day_num = args
+            .next()
+            .with_context(|| format!("missing day number\n{USAGE}"))?;
+        let day_num = day_num.parse().context("invalid day number")?;
+        let title = args
+            .next()
+            .with_context(|| format!("missing day title\n{USAGE}"))?;
+        options.new_day = Some((day_num, title));
+    } else if args.next_if_eq("serve").is_some() {
+        options.serve = true;
+    } else if args.next_if_eq("generate").is_some() {
+        options.generate = true;
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => options.input = Some(args.next().context("--input requires a path")?),
+            "--format" => {
+                let format = args.next().context("--format requires a format name")?;
+                options.format = format.parse()?;
+            }
+            "--part" => {
+                let part = args.next().context("--part requires a part number")?;
+                ensure!(part == "1" || part == "2", "part number must be 1 or 2");
+                options.part = Some(part.parse()?);
+            }
+            "--check" => options.check = true,
+            "--update-answers" => options.update_answers = true,
+            "--input-dir" => {
+                options.input_dir = Some(args.next().context("--input-dir requires a path")?)
+            }
+            "--inputs-dir" => {
+                options.inputs_dir = Some(args.next().context("--inputs-dir requires a path")?)
+            }
+            "--strict-input" => options.strict_input = true,
+            "--live" => options.live = true,
+            "--bench" => options.bench = true,
+            "--runs" => {
+                let runs = args.next().context("--runs requires a number")?;
+                let runs = runs.parse().context("invalid number of runs")?;
+                ensure!(runs > 0, "number of runs must be positive");
+                options.runs = Some(runs);
+            }
+            "--mem" => options.mem = true,
+            "--fuzz" => options.fuzz = true,
+            "--fuzz-worker" => options.fuzz_worker = true,
+            "--size" => {
+                let size = args.next().context("--size requires a number")?;
+                options.size = Some(size.parse().context("invalid size")?);
+            }
+            "--seed" => {
+                let seed = args.next().context("--seed requires a number")?;
+                options.seed = Some(seed.parse().context("invalid seed")?);
+            }
+            "--trace" => options.trace = true,
+            "--trace-file" => {
+                options.trace = true;
+                options.trace_file = Some(args.next().context("--trace-file requires a path")?);
+            }
+            "--compare" => {
+                let pct = options.compare.unwrap_or(history::DEFAULT_MAX_SLOWDOWN_PCT);
+                options.compare = Some(pct);
+            }
+            "--compare-threshold" => {
+                let pct = args
+                    .next()
+                    .context("--compare-threshold requires a percentage")?;
+                options.compare = Some(pct.parse().context("invalid percentage")?);
+            }
+            "--day-budget" => {
+                let budget = args.next().context("--day-budget requires a duration")?;
+                let (day, ms) = match budget.split_once('=') {
+                    Some((day, ms)) => (Some(day.parse().context("invalid budget day")?), ms),
+                    None => (None, budget.as_str()),
+                };
+                let ms = ms.parse().context("invalid budget duration")?;
+                options.day_budgets.push((day, ms));
+            }
+            "--total-budget" => {
+                let ms = args.next().context("--total-budget requires a duration")?;
+                options.total_budget = Some(ms.parse().context("invalid budget duration")?);
+            }
+            "--timeout" => {
+                let secs = args
+                    .next()
+                    .context("--timeout requires a number of seconds")?;
+                let secs = secs.parse().context("invalid timeout")?;
+                options.timeout = Some(time::Duration::try_from_secs_f64(secs)?);
+            }
+            "--jobs" => {
+                let jobs = args.next().context("--jobs requires a number")?;
+                let jobs = jobs.parse().context("invalid number of jobs")?;
+                ensure!(jobs > 0, "number of jobs must be positive");
+                options.jobs = Some(jobs);
+            }
+            "--sequential" => options.jobs = Some(1),
+            "--year" => {
+                let year = args.next().context("--year requires a year")?;
+                ensure!(is_year(&year), "invalid year '{year}'");
+                options.year = Some(year.parse()?);
+            }
+            "--port" => {
+                let port = args.next().context("--port requires a port number")?;
+                options.port = Some(port.parse().context("invalid port number")?);
+            }
+            "--variant" => {
+                options.variant = Some(args.next().context("--variant requires a name")?)
+            }
+            "--cross-check" => options.cross_check = true,
+            "--list" => options.list = true,
+            "--samples" => options.samples = true,
+            "--except" => {
+                let days = args.next().context("--except requires a list of days")?;
+                options.except.extend(parse_days(&days)?);
+            }
+            "-h" | "--help" => options.help = true,
+            _ if is_year(&arg) => options.year = Some(arg.parse()?),
+            _ if !arg.starts_with('-') => options.days.extend(parse_days(&arg)?),
+            _ => bail!("unknown option '{arg}'\n{USAGE}"),
+        }
+    }
+    Ok(options)
 }
 
-fn format_time_annotation(elapsed: time::Duration) -> String {
-    // Don't output duration if it's insignificant or we're not on a TTY (e.g. stdout is piped).
-    if elapsed.as_millis() < 1 || !std::io::stdout().is_terminal() {
-        "".to_string()
-    } else {
-        format!(" ({elapsed:.0?})")
+// Parses a day selection like "17", "5-10", "1,3,17", "odd", "even" or "all".
+fn parse_days(s: &str) -> aoc::Result<BTreeSet<usize>> {
+    let all_days = 1..=25;
+    let mut days = BTreeSet::new();
+    for item in s.split(',') {
+        match item {
+            "all" => days.extend(all_days.clone()),
+            "odd" => days.extend(all_days.clone().filter(|n| n % 2 == 1)),
+            "even" => days.extend(all_days.clone().filter(|n| n % 2 == 0)),
+            _ => {
+                let (start, end) = item.split_once('-').unwrap_or((item, item));
+                let [start, end] = [start, end].map(|n| n.parse::<usize>());
+                let (Ok(start), Ok(end)) = (start, end) else {
+                    bail!("invalid day selection '{item}'\n{USAGE}");
+                };
+                ensure!(start <= end, "invalid day range '{item}'");
+                ensure!(
+                    all_days.contains(&start) && all_days.contains(&end),
+                    "day number out of range in '{item}'"
+                );
+                days.extend(start..=end);
+            }
+        }
     }
+    Ok(days)
+}
+
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
+// Whether an argument is a year, like 2024, rather than a day selection.
+fn is_year(arg: &str) -> bool {
+    arg.len() == 4 && arg.parse().is_ok_and(|year: usize| year >= 2015)
+}
+
+fn input_dir(options: &Options) -> String {
+    let input_dir = options.input_dir.clone();
+    input_dir
+        .or_else(|| env::var("AOC_INPUT_DIR").ok())
+        .unwrap_or_else(|| DEFAULT_INPUT_DIR.to_string())
+}
+
+fn default_input_path(input_dir: &str, year: usize, day_num: usize) -> String {
+    format!("{input_dir}/{year}/{day_num:02}.txt")
+}
+
+#[test]
+fn parse_days_test() {
+    let days = |s| parse_days(s).map(|days| days.into_iter().collect_vec());
+    assert_eq!(days("17").unwrap(), [17]);
+    assert_eq!(days("5-8").unwrap(), [5, 6, 7, 8]);
+    assert_eq!(days("17,3,1").unwrap(), [1, 3, 17]);
+    assert_eq!(days("1-3,2,24-25").unwrap(), [1, 2, 3, 24, 25]);
+    assert_eq!(days("odd").unwrap().len(), 13);
+    assert_eq!(days("even,25").unwrap().len(), 13);
+    assert_eq!(days("all").unwrap().len(), 25);
+    assert!(days("").is_err());
+    assert!(days("0").is_err());
+    assert!(days("26").is_err());
+    assert!(days("10-5").is_err());
+    assert!(days("x").is_err());
+}
+
+#[test]
+fn parse_year_test() {
+    let args = |s: &str| s.split(' ').map(str::to_string).collect_vec();
+    let options = parse_options(args("2024 17").into_iter()).unwrap();
+    assert_eq!(options.year, Some(2024));
+    assert_eq!(options.days.into_iter().collect_vec(), [17]);
+    let options = parse_options(args("--year 2025 1-2").into_iter()).unwrap();
+    assert_eq!(options.year, Some(2025));
+    assert!(parse_options(args("--year 25").into_iter()).is_err());
+    let options = parse_options(args("new 2025 1 Title").into_iter()).unwrap();
+    assert_eq!(options.year, Some(2025));
+    assert_eq!(options.new_day, Some((1, "Title".to_string())));
+    let err = parse_options(args("new 2025").into_iter()).err().unwrap();
+    assert_eq!(err.to_string(), format!("missing day number\n{USAGE}"));
+}
+
+#[test]
+fn parse_generate_test() {
+    let args = "generate 2024 16 --size 21 --seed 3"
+        .split(' ')
+        .map(str::to_string);
+    let options = parse_options(args).unwrap();
+    assert!(options.generate);
+    assert_eq!(options.days.into_iter().collect_vec(), [16]);
+    assert_eq!((options.size, options.seed), (Some(21), Some(3)));
+}
+
+#[test]
+fn parse_flags_test() {
+    let args = |s: &str| s.split(' ').map(str::to_string).collect_vec().into_iter();
+    let options = parse_options(args("--bench 6")).unwrap();
+    assert!(options.bench);
+    assert_eq!(options.days.into_iter().collect_vec(), [6]);
+    let options = parse_options(args("--fuzz 13 --runs 50")).unwrap();
+    assert!(options.fuzz);
+    assert_eq!(options.runs, Some(50));
+    assert_eq!(options.days.into_iter().collect_vec(), [13]);
+    let options = parse_options(args("--trace-file 6 16")).unwrap();
+    assert!(options.trace);
+    assert_eq!(options.trace_file.as_deref(), Some("6"));
+    assert_eq!(options.days.into_iter().collect_vec(), [16]);
+    let options = parse_options(args("--compare 16")).unwrap();
+    assert_eq!(options.compare, Some(history::DEFAULT_MAX_SLOWDOWN_PCT));
+    assert_eq!(options.days.into_iter().collect_vec(), [16]);
+    let options = parse_options(args("16 --compare-threshold 25")).unwrap();
+    assert_eq!(options.compare, Some(25.0));
+    let options = parse_options(args("--compare-threshold 25 --compare")).unwrap();
+    assert_eq!(options.compare, Some(25.0));
 }
@@ -0,0 +1,169 @@
+use crate::runner::{self, Job};
+use anyhow::ensure;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed};
+use std::time::Instant;
+
+// The system allocator, but counting allocations while measuring. Otherwise, the only overhead is
+// checking whether it's measuring.
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static MEASURING: AtomicBool = AtomicBool::new(false);
+static ALLOC_COUNT: AtomicUsize = AtomicUsize::new(0);
+static ALLOC_BYTES: AtomicUsize = AtomicUsize::new(0);
+// Memory allocated before measuring can be freed while measuring, so this can go negative.
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+static PEAK_LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size(), 0);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_alloc(layout.size(), 0);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if MEASURING.load(Relaxed) {
+            LIVE_BYTES.fetch_sub(layout.size() as isize, Relaxed);
+        }
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    // Reallocations count as new allocations of the new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_alloc(new_size, layout.size());
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn record_alloc(size: usize, freed_size: usize) {
+    if MEASURING.load(Relaxed) {
+        ALLOC_COUNT.fetch_add(1, Relaxed);
+        ALLOC_BYTES.fetch_add(size, Relaxed);
+        let live_diff = size as isize - freed_size as isize;
+        let live = LIVE_BYTES.fetch_add(live_diff, Relaxed) + live_diff;
+        PEAK_LIVE_BYTES.fetch_max(live, Relaxed);
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct MemStats {
+    alloc_count: usize,
+    alloc_bytes: usize,
+    peak_live_bytes: usize,
+}
+
+// Runs `f` counting the allocations it makes. Allocations on other threads are counted too, so
+// nothing else should be running meanwhile.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, MemStats) {
+    for counter in [&ALLOC_COUNT, &ALLOC_BYTES] {
+        counter.store(0, Relaxed);
+    }
+    for counter in [&LIVE_BYTES, &PEAK_LIVE_BYTES] {
+        counter.store(0, Relaxed);
+    }
+    MEASURING.store(true, Relaxed);
+    let result = f();
+    MEASURING.store(false, Relaxed);
+    let stats = MemStats {
+        alloc_count: ALLOC_COUNT.load(Relaxed),
+        alloc_bytes: ALLOC_BYTES.load(Relaxed),
+        peak_live_bytes: PEAK_LIVE_BYTES.load(Relaxed) as usize,
+    };
+    (result, stats)
+}
+
+// Runs each day once, one after the other, and prints its run time along with the number of
+// allocations, bytes allocated and peak memory usage (not counting the input itself).
+pub fn run_mem_profile(jobs: &[Job]) -> aoc::Result<()> {
+    println!(
+        "{:<7} {:>10} {:>10} {:>12} {:>12}",
+        "", "time", "allocs", "allocated", "peak"
+    );
+    let mut total = MemStats::default();
+    let mut fail_count = 0;
+    for job in jobs {
+        let day = format!("Day {}", job.day_num);
+        let result = runner::run_on_day_thread(|| {
+            let input = job.read_input()?;
+            let instant = Instant::now();
+            let (answer, stats) = measure(|| (job.solver)(&input, &job.params));
+            let elapsed = instant.elapsed();
+            answer.map_err(|err| aoc::locate_error(err, &input, job.input_name()))?;
+            Ok((elapsed, stats))
+        });
+        let (elapsed, stats) = match result {
+            Ok(measurement) => measurement,
+            Err(err) => {
+                // Failed days still go on the table, so the rest of the days can be measured.
+                fail_count += 1;
+                println!("{day:<7} FAILED ({err:#})");
+                continue;
+            }
+        };
+        total.alloc_count += stats.alloc_count;
+        total.alloc_bytes += stats.alloc_bytes;
+        total.peak_live_bytes = total.peak_live_bytes.max(stats.peak_live_bytes);
+        println!(
+            "{day:<7} {:>10} {:>10} {:>12} {:>12}",
+            format!("{elapsed:.2?}"),
+            stats.alloc_count,
+            format_bytes(stats.alloc_bytes),
+            format_bytes(stats.peak_live_bytes),
+        );
+    }
+    println!(
+        "{:<7} {:>10} {:>10} {:>12} {:>12}",
+        "Total",
+        "",
+        total.alloc_count,
+        format_bytes(total.alloc_bytes),
+        format_bytes(total.peak_live_bytes),
+    );
+    ensure!(
+        fail_count == 0,
+        "{fail_count} of {} days failed",
+        jobs.len()
+    );
+    Ok(())
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    match bytes as f64 {
+        b if b < KIB => format!("{bytes} B"),
+        b if b < KIB * KIB => format!("{:.1} KiB", b / KIB),
+        b if b < KIB * KIB * KIB => format!("{:.1} MiB", b / (KIB * KIB)),
+        b => format!("{:.1} GiB", b / (KIB * KIB * KIB)),
+    }
+}
+
+#[test]
+fn measure_test() {
+    let (vec, stats) = measure(|| {
+        let mut vec = Vec::<u8>::with_capacity(1000);
+        vec.reserve_exact(2000);
+        drop(vec![0u8; 500]);
+        vec
+    });
+    assert_eq!(vec.capacity(), 2000);
+    // Other tests may be allocating at the same time, so these are lower bounds. The peak memory
+    // usage cannot be checked, as other tests may free memory meanwhile.
+    assert!(stats.alloc_count >= 3);
+    assert!(stats.alloc_bytes >= 3500);
+}
+
+#[test]
+fn format_bytes_test() {
+    assert_eq!(format_bytes(12), "12 B");
+    assert_eq!(format_bytes(2560), "2.5 KiB");
+    assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    assert_eq!(format_bytes(2671 * 1024 * 1024), "2.6 GiB");
+}
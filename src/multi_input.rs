@@ -0,0 +1,140 @@
+use crate::check::compare_parts;
+use crate::runner::DayResult;
+use anyhow::{Context, ensure};
+use itertools::Itertools;
+use std::fs;
+use std::path::Path;
+
+// An input file named like "NN-<name>.txt" on a multi-input directory.
+pub struct NamedInput {
+    pub day_num: usize,
+    pub name: String,
+    pub path: String,
+    answer_path: String,
+}
+
+// Finds the "NN-<name>.txt" input files for the given days on `dir`, sorted by day and name.
+pub fn find_inputs(dir: &str, day_nums: &[usize]) -> aoc::Result<Vec<NamedInput>> {
+    let entries = fs::read_dir(dir).with_context(|| format!("error reading {dir}"))?;
+    let mut inputs = vec![];
+    for entry in entries {
+        let file_name = entry
+            .with_context(|| format!("error reading {dir}"))?
+            .file_name();
+        let Some((day_num, name)) = file_name.to_str().and_then(parse_input_name) else {
+            continue;
+        };
+        if day_nums.contains(&day_num) {
+            let path = format!("{dir}/{day_num:02}-{name}");
+            inputs.push(NamedInput {
+                day_num,
+                name: name.to_string(),
+                path: format!("{path}.txt"),
+                answer_path: format!("{path}.answer"),
+            });
+        }
+    }
+    ensure!(
+        !inputs.is_empty(),
+        "no NN-<name>.txt inputs for the selected days found in {dir}"
+    );
+    inputs.sort_by(|a, b| (a.day_num, &a.name).cmp(&(b.day_num, &b.name)));
+    Ok(inputs)
+}
+
+fn parse_input_name(file_name: &str) -> Option<(usize, &str)> {
+    let (day, name) = file_name.strip_suffix(".txt")?.split_once('-')?;
+    let day_num = day.parse().ok().filter(|_| day.len() == 2)?;
+    (!name.is_empty()).then_some((day_num, name))
+}
+
+// Prints a matrix with a row per day and a column per input name, showing whether each result
+// matches the input's "NN-<name>.answer" file and its run time. The details of the failures are
+// printed below it. Fails if any input failed to run or got a wrong answer.
+pub fn check_inputs(
+    inputs: &[NamedInput],
+    results: impl Iterator<Item = DayResult>,
+    part: Option<usize>,
+) -> aoc::Result<()> {
+    let names = inputs.iter().map(|input| &input.name).sorted().dedup();
+    let names = names.collect_vec();
+    let width = names
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(12);
+    print_row("", names.iter().map(|n| n.as_str()), width);
+
+    let mut failures = vec![];
+    let results = inputs.iter().zip(results);
+    for (day_num, day_results) in &results.chunk_by(|(input, _)| input.day_num) {
+        let mut cells = vec![String::new(); names.len()];
+        for (input, result) in day_results {
+            let cell = match check_input(input, result, part) {
+                Ok(cell) => cell,
+                Err(failure) => {
+                    failures.push(format!("Day {day_num} {}: {failure}", input.name));
+                    "FAIL".to_string()
+                }
+            };
+            let column = names.iter().position(|&n| *n == input.name).unwrap();
+            cells[column] = cell;
+        }
+        // Days without an input for some name get a "-" cell.
+        let cells = cells.iter().map(|c| if c.is_empty() { "-" } else { c });
+        print_row(&format!("Day {day_num}"), cells, width);
+    }
+
+    if !failures.is_empty() {
+        println!();
+        for failure in &failures {
+            println!("{failure}");
+        }
+    }
+    ensure!(
+        failures.is_empty(),
+        "{} of {} inputs failed",
+        failures.len(),
+        inputs.len()
+    );
+    Ok(())
+}
+
+fn print_row<'a>(label: &str, cells: impl Iterator<Item = &'a str>, width: usize) {
+    let row = cells.map(|cell| format!("{cell:<width$}")).join(" ");
+    println!("{:<7} {}", label, row.trim_end());
+}
+
+// Returns the matrix cell for a successful input, or a description of the failure.
+fn check_input(
+    input: &NamedInput,
+    result: DayResult,
+    part: Option<usize>,
+) -> Result<String, String> {
+    let (answers, elapsed) = result.map_err(|err| format!("FAILED ({err:#})"))?;
+    let answer_path = &input.answer_path;
+    if !Path::new(answer_path).exists() {
+        // Nothing to compare with, but at least it ran.
+        return Ok(format!("DONE {elapsed:.0?}"));
+    }
+    let expected = fs::read_to_string(answer_path)
+        .map_err(|err| format!("error reading {answer_path}: {err}"))?;
+    for (part_num, e, a) in compare_parts(expected.trim_end(), &answers, part) {
+        if e != a {
+            let (e, a) = (e.unwrap_or("nothing"), a.unwrap_or("nothing"));
+            return Err(format!("part {part_num} expected {e}, got {a}"));
+        }
+    }
+    Ok(format!("PASS {elapsed:.0?}"))
+}
+
+#[test]
+fn parse_input_name_test() {
+    assert_eq!(parse_input_name("17-alice.txt"), Some((17, "alice")));
+    assert_eq!(parse_input_name("05-bob-2.txt"), Some((5, "bob-2")));
+    assert_eq!(parse_input_name("17.txt"), None);
+    assert_eq!(parse_input_name("17-.txt"), None);
+    assert_eq!(parse_input_name("7-alice.txt"), None);
+    assert_eq!(parse_input_name("17-alice.answer"), None);
+}
@@ -0,0 +1,175 @@
+use crate::runner::DayResult;
+use anyhow::bail;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Default, Clone, Copy)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> aoc::Result<Format> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("unknown output format '{s}'; expected text, json or csv"),
+        }
+    }
+}
+
+// Prints the results of running each day as they come, optionally showing only one of the parts.
+// Failed days are reported along with the others. Returns the number of failed days.
+pub fn print_results(
+    format: Format,
+    part: Option<usize>,
+    results: impl Iterator<Item = (usize, String, DayResult)>,
+) -> usize {
+    match format {
+        Format::Text => {}
+        Format::Json => print!("["),
+        Format::Csv => println!("day,part1,part2,elapsed_ms,input,error"),
+    }
+    let mut fail_count = 0;
+    for (i, (day_num, input_path, result)) in results.enumerate() {
+        fail_count += result.is_err() as usize;
+        match format {
+            Format::Text => print_text_record(day_num, &result, part),
+            Format::Json => {
+                let separator = if i == 0 { "" } else { "," };
+                let [part_1, part_2, elapsed_ms, error] = record_fields(&result, part);
+                let [part_1, part_2, error] = [part_1, part_2, error].map(|f| match f {
+                    Some(s) => json_string(&s),
+                    None => "null".to_string(),
+                });
+                let elapsed_ms = elapsed_ms.unwrap_or("null".to_string());
+                let input_path = json_string(&input_path);
+                print!(
+                    "{separator}\n  {{\"day\": {day_num}, \"part1\": {part_1}, \"part2\": {part_2}, \
+                    \"elapsed_ms\": {elapsed_ms}, \"input\": {input_path}, \"error\": {error}}}"
+                );
+            }
+            Format::Csv => {
+                let [part_1, part_2, elapsed_ms, error] =
+                    record_fields(&result, part).map(|f| csv_field(&f.unwrap_or_default()));
+                let input_path = csv_field(&input_path);
+                println!("{day_num},{part_1},{part_2},{elapsed_ms},{input_path},{error}");
+            }
+        }
+    }
+    if let Format::Json = format {
+        println!("\n]");
+    }
+    fail_count
+}
+
+fn print_text_record(day_num: usize, result: &DayResult, part: Option<usize>) {
+    let (answers, elapsed) = match result {
+        Ok(res) => res,
+        Err(err) => {
+            // Input errors come with a snippet of the input on the lines after the message.
+            let message = format!("{err:#}");
+            let (message, snippet) = message.split_once('\n').unwrap_or((&message, ""));
+            println!("Day {day_num}: FAILED ({message})");
+            if !snippet.is_empty() {
+                println!("{snippet}");
+            }
+            return;
+        }
+    };
+    let answer = format_answer(answers, part);
+    let time_annotation = format_time_annotation(*elapsed);
+    println!("Day {day_num}{time_annotation}: {answer}");
+}
+
+// Formats the answers of a day, or only the answer of the given part.
+pub fn format_answer(answers: &aoc::Answers, part: Option<usize>) -> String {
+    match part {
+        Some(part_num) => answers.part(part_num).unwrap_or_default().to_string(),
+        None => answers.to_string(),
+    }
+}
+
+// Returns the part 1, part 2, elapsed milliseconds and error fields of a day's record. The part that
+// was not selected, if any, is left empty.
+pub fn record_fields(result: &DayResult, part: Option<usize>) -> [Option<String>; 4] {
+    match result {
+        Ok((answers, elapsed)) => {
+            let [part_1, part_2] = [1, 2].map(|part_num| {
+                let selected = part.is_none_or(|p| p == part_num);
+                answers
+                    .part(part_num)
+                    .filter(|_| selected)
+                    .map(str::to_string)
+            });
+            let elapsed_ms = format!("{:.3}", elapsed.as_secs_f64() * 1000.0);
+            [part_1, part_2, Some(elapsed_ms), None]
+        }
+        Err(err) => [None, None, None, Some(format!("{err:#}"))],
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => json.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Whether the run times of days are shown on the output. On the text format, they are only shown on
+// a TTY (i.e. not when stdout is piped).
+pub fn shows_timings(format: Format) -> bool {
+    match format {
+        Format::Text => io::stdout().is_terminal(),
+        Format::Json | Format::Csv => true,
+    }
+}
+
+fn format_time_annotation(elapsed: Duration) -> String {
+    // Don't output duration if it's insignificant or we're not on a TTY.
+    if elapsed.as_millis() < 1 || !shows_timings(Format::Text) {
+        "".to_string()
+    } else {
+        format!(" ({elapsed:.0?})")
+    }
+}
+
+#[test]
+fn json_string_test() {
+    assert_eq!(json_string("1366"), r#""1366""#);
+    assert_eq!(json_string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+    assert_eq!(json_string("\x07"), r#""\u0007""#);
+}
+
+#[test]
+fn csv_field_test() {
+    assert_eq!(csv_field("1366"), "1366");
+    assert_eq!(csv_field("2,0,4"), "\"2,0,4\"");
+    assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+}
@@ -0,0 +1,276 @@
+use anyhow::{Context, anyhow};
+use aoc::days::Solver;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{fs, io, iter, thread};
+
+pub type DayResult = aoc::Result<(aoc::Answers, Duration)>;
+
+#[derive(Clone)]
+pub struct Job {
+    pub day_num: usize,
+    pub variant: &'static str,
+    pub solver: Solver,
+    pub params: aoc::Params,
+    pub input_path: String,
+    // The input itself, when it doesn't come from a file of its own. `input_path` then tells where
+    // it comes from.
+    pub input: Option<String>,
+    // The input baked into the executable for the day, for when there's no file at `input_path`.
+    pub embedded_input: Option<&'static str>,
+    // Whether to fail on inputs that need normalizing, instead of warning about them.
+    pub strict_input: bool,
+}
+
+impl Job {
+    pub fn new(day_num: usize, variant: &'static str, solver: Solver, input_path: String) -> Job {
+        Job {
+            day_num,
+            variant,
+            solver,
+            params: aoc::Params::default(),
+            input_path,
+            input: None,
+            embedded_input: None,
+            strict_input: false,
+        }
+    }
+
+    // Reads the job's input and normalizes it with `aoc::normalize_input()`. Its issues are printed
+    // as warnings, or returned as an error on strict mode.
+    pub fn read_input(&self) -> aoc::Result<String> {
+        let input = match &self.input {
+            Some(input) => input.clone(),
+            None => match (read_input(&self.input_path), self.embedded_input) {
+                (Err(err), Some(embedded)) if is_not_found(&err) => embedded.to_string(),
+                (result, _) => result?,
+            },
+        };
+        let (normalized, issues) = aoc::normalize_input(&input);
+        for issue in issues {
+            let issue = aoc::locate_error(issue.into(), &input, self.input_name());
+            if self.strict_input {
+                return Err(issue);
+            }
+            eprintln!("warning: {issue}");
+        }
+        Ok(normalized.into_owned())
+    }
+
+    pub fn input_name(&self) -> &str {
+        if self.input_path == "-" {
+            "stdin"
+        } else {
+            &self.input_path
+        }
+    }
+}
+
+// Runs the day jobs on a pool of `workers` threads and returns their results in the same order as
+// the given jobs. Failures of any kind (errors, panics or timeouts) are reported as that job's error,
+// so they don't affect other days.
+pub fn run_days(
+    jobs: &[Job],
+    workers: usize,
+    timeout: Option<Duration>,
+) -> impl Iterator<Item = DayResult> + use<> {
+    let mut updates = watch_days(jobs, workers, timeout);
+    let mut results: Vec<Option<DayResult>> = jobs.iter().map(|_| None).collect();
+    let mut next = 0;
+    iter::from_fn(move || {
+        // Results that come before the next one in order are kept until it's their turn.
+        while next < results.len() && results[next].is_none() {
+            match updates.next() {
+                Some((i, DayUpdate::Finished(result))) => results[i] = Some(result),
+                Some((_, DayUpdate::Started(_))) => {}
+                None => results[next] = Some(Err(anyhow!("worker thread disconnected"))),
+            }
+        }
+        next += 1;
+        results.get_mut(next - 1)?.take()
+    })
+}
+
+pub enum DayUpdate {
+    Started(Instant),
+    Finished(DayResult),
+}
+
+// Like `run_days()`, but returns updates about the jobs as they happen, along with the index of their
+// job: when each one starts running, and when it finishes.
+pub fn watch_days(
+    jobs: &[Job],
+    workers: usize,
+    timeout: Option<Duration>,
+) -> impl Iterator<Item = (usize, DayUpdate)> + use<> {
+    let (sender, receiver) = mpsc::channel();
+    let queue: VecDeque<_> = jobs.iter().cloned().enumerate().collect();
+    let queue = Arc::new(Mutex::new(queue));
+    let spawn_worker = move || {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        thread::Builder::new()
+            .name(WORKER_THREAD_NAME.to_string())
+            .spawn(move || {
+                while let Some((i, job)) = pop_job(&queue) {
+                    _ = sender.send((i, DayUpdate::Started(Instant::now())));
+                    _ = sender.send((i, DayUpdate::Finished(run_isolated(&job))));
+                }
+            })
+            .expect("failed to spawn worker thread");
+    };
+    for _ in 0..workers.min(jobs.len()) {
+        spawn_worker();
+    }
+
+    // The start time of the running jobs, and whether each job is done, for reporting timeouts.
+    let mut running: Vec<Option<Instant>> = vec![None; jobs.len()];
+    let mut done = vec![false; jobs.len()];
+    iter::from_fn(move || {
+        while done.contains(&false) {
+            let next_timeout = timeout.and_then(|timeout| {
+                let running = running.iter().enumerate();
+                let deadlines = running.filter_map(|(i, start)| Some((i, (*start)? + timeout)));
+                deadlines.min_by_key(|&(_, deadline)| deadline)
+            });
+            let update = match next_timeout {
+                Some((_, deadline)) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(RecvTimeoutError::from),
+            };
+            match update {
+                // Days that timed out already have their result.
+                Ok((i, DayUpdate::Finished(_))) if done[i] => continue,
+                Ok((i, update)) => {
+                    match update {
+                        DayUpdate::Started(start) => running[i] = Some(start),
+                        DayUpdate::Finished(_) => (running[i], done[i]) = (None, true),
+                    }
+                    return Some((i, update));
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let (i, _) = next_timeout?;
+                    (running[i], done[i]) = (None, true);
+                    // There's no way of cancelling a thread, so the timed-out day keeps its worker
+                    // busy until the process exits. Spawn a new worker in its place to run the rest
+                    // of days.
+                    spawn_worker();
+                    let timeout = timeout.unwrap_or_default();
+                    return Some((
+                        i,
+                        DayUpdate::Finished(Err(anyhow!("timed out after {timeout:?}"))),
+                    ));
+                }
+                // Not really possible, as `spawn_worker` keeps a sender for new workers.
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+        None
+    })
+}
+
+fn pop_job(queue: &Mutex<VecDeque<(usize, Job)>>) -> Option<(usize, Job)> {
+    queue.lock().unwrap().pop_front()
+}
+
+pub fn run_single_day(job: &Job) -> DayResult {
+    let instant = Instant::now();
+    let input = job.read_input()?;
+    let answer = (job.solver)(&input, &job.params)
+        .map_err(|err| aoc::locate_error(err, &input, job.input_name()))?;
+    Ok((answer, instant.elapsed()))
+}
+
+// Like `run_single_day()`, but turns panics into errors.
+fn run_isolated(job: &Job) -> DayResult {
+    catch_panic(|| run_single_day(job)).unwrap_or_else(|message| Err(anyhow!(message)))
+}
+
+// Runs `f` on a day thread of its own and waits for it, turning panics into errors. For running days
+// one at a time outside of the worker pool.
+pub fn run_on_day_thread<T: Send>(f: impl FnOnce() -> aoc::Result<T> + Send) -> aoc::Result<T> {
+    thread::scope(|scope| {
+        let name = format!("{DAY_THREAD_PREFIX}thread");
+        let day_thread = thread::Builder::new()
+            .name(name)
+            .spawn_scoped(scope, || catch_panic(f));
+        let result = day_thread?
+            .join()
+            .unwrap_or_else(|_| Err("panicked".to_string()));
+        result.unwrap_or_else(|message| Err(anyhow!(message)))
+    })
+}
+
+// Runs `f` and returns the message of its panic, if it panics. Must be called on a day thread, whose
+// panic messages are kept by the panic hook.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| {
+        PANIC_MESSAGE
+            .take()
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+// Reads the input file at `path`, or the standard input if `path` is "-".
+fn read_input(path: &str) -> aoc::Result<String> {
+    if path == "-" {
+        return io::read_to_string(io::stdin()).context("error reading standard input");
+    }
+    fs::read_to_string(path).with_context(|| format!("error reading {path}"))
+}
+
+fn is_not_found(err: &anyhow::Error) -> bool {
+    let err = err.downcast_ref::<io::Error>();
+    err.is_some_and(|err| err.kind() == io::ErrorKind::NotFound)
+}
+
+thread_local! {
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Threads that run days have names starting with this, so their panics are kept by the panic hook.
+pub const DAY_THREAD_PREFIX: &str = "day ";
+const WORKER_THREAD_NAME: &str = "day worker";
+
+// Keeps panic messages of day threads for reporting them along with the rest of the day results,
+// instead of printing them to stderr as they happen. Panics on other threads are printed as usual.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let is_day_thread = thread::current()
+            .name()
+            .is_some_and(|n| n.starts_with(DAY_THREAD_PREFIX));
+        if !is_day_thread {
+            return default_hook(info);
+        }
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        let location = info
+            .location()
+            .map_or(String::new(), |l| format!(" at {l}"));
+        PANIC_MESSAGE.set(Some(format!("panicked{location}: {message}")));
+    }));
+}
+
+#[test]
+fn embedded_input_test() {
+    let solver: Solver = |input, _| aoc::answer(input.len());
+    let mut job = Job::new(1, "default", solver, "inputs/missing.txt".to_string());
+    assert!(job.read_input().is_err());
+    job.embedded_input = Some("embedded\n");
+    assert_eq!(job.read_input().unwrap(), "embedded\n");
+    // Files that exist are preferred, and errors other than missing files are not hidden.
+    job.input_path = "Cargo.toml".to_string();
+    assert!(job.read_input().unwrap().starts_with("[package]"));
+    job.input_path = "src".to_string();
+    assert!(job.read_input().is_err());
+}
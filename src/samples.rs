@@ -0,0 +1,156 @@
+use crate::multi_input;
+use crate::runner::{DayResult, Job};
+use anyhow::{Context, bail};
+use aoc::days::Solver;
+use std::fs;
+
+pub fn samples_dir(year: usize) -> String {
+    format!("samples/{year}")
+}
+
+// A puzzle sample with its expected answers, from a "NN-<name>.txt" file on the samples directory.
+// These files have a header with the expected answers of each part and, optionally, the parameters
+// for running the sample, followed by a "---" line and the sample input:
+//
+//     params: width=11 height=7
+//     part1: 12
+//     ---
+//     p=0,4 v=3,-3
+//     ...
+pub struct Sample {
+    pub day_num: usize,
+    pub name: String,
+    pub path: String,
+    pub params: aoc::Params,
+    pub expected: [Option<String>; 2],
+    pub input: String,
+}
+
+impl Sample {
+    pub fn job(&self, variant: &'static str, solver: Solver) -> Job {
+        let mut job = Job::new(self.day_num, variant, solver, self.path.clone());
+        job.params = self.params.clone();
+        job.input = Some(self.input.clone());
+        job
+    }
+}
+
+pub fn load_samples(dir: &str, day_nums: &[usize]) -> aoc::Result<Vec<Sample>> {
+    let files = multi_input::find_inputs(dir, day_nums)?;
+    files
+        .into_iter()
+        .map(|file| {
+            let contents = fs::read_to_string(&file.path)
+                .with_context(|| format!("error reading {}", file.path))?;
+            parse_sample(&contents)
+                .map(|(params, expected, input)| Sample {
+                    day_num: file.day_num,
+                    name: file.name,
+                    params,
+                    expected,
+                    input: input.to_string(),
+                    path: file.path.clone(),
+                })
+                .with_context(|| format!("invalid sample file {}", file.path))
+        })
+        .collect()
+}
+
+type SampleParts<'a> = (aoc::Params, [Option<String>; 2], &'a str);
+
+fn parse_sample(contents: &str) -> aoc::Result<SampleParts<'_>> {
+    let (header, input) = match contents.split_once("\n---\n") {
+        Some(parts) => parts,
+        None => (
+            "",
+            contents
+                .strip_prefix("---\n")
+                .context("--- line not found")?,
+        ),
+    };
+    let mut params = aoc::Params::default();
+    let mut expected = [None, None];
+    for line in header.lines() {
+        match line.split_once(": ") {
+            Some(("params", value)) => params = value.parse()?,
+            Some(("part1", value)) => expected[0] = Some(value.to_string()),
+            Some(("part2", value)) => expected[1] = Some(value.to_string()),
+            _ => bail!("invalid header line '{line}'"),
+        }
+    }
+    Ok((params, expected, input))
+}
+
+// Compares the results of running the samples with their expected answers (only for the given part,
+// if any), and prints a PASS/FAIL line for each of them. Fails if any of them doesn't match.
+pub fn check_samples(
+    samples: &[Sample],
+    results: impl Iterator<Item = DayResult>,
+    part: Option<usize>,
+) -> aoc::Result<()> {
+    let name_width = samples.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let mut fail_count = 0;
+    for (sample, result) in samples.iter().zip(results) {
+        let day = format!("Day {}", sample.day_num);
+        let name = format!("{:<name_width$}", sample.name);
+        let answers = match result {
+            Ok((answers, _)) => answers,
+            Err(err) => {
+                println!("{day:<7} {name}  {:<6}  FAIL  {err:#}", "");
+                fail_count += 1;
+                continue;
+            }
+        };
+        for (part_num, expected) in (1..).zip(&sample.expected) {
+            let Some(expected) = expected.as_deref() else {
+                continue;
+            };
+            if part.is_some_and(|p| p != part_num) {
+                continue;
+            }
+            match answers.part(part_num) {
+                Some(actual) if actual == expected => {
+                    println!("{day:<7} {name}  part {part_num}  PASS  {actual}")
+                }
+                actual => {
+                    let actual = actual.unwrap_or("nothing");
+                    println!(
+                        "{day:<7} {name}  part {part_num}  FAIL  expected {expected}, got {actual}"
+                    );
+                    fail_count += 1;
+                }
+            }
+        }
+    }
+    if fail_count > 0 {
+        bail!("samples don't match their expected answers ({fail_count} failures)");
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_sample_test() {
+    let (params, expected, input) = parse_sample("params: a=1\npart2: x y\n---\n1\n2\n").unwrap();
+    assert_eq!(params, "a=1".parse().unwrap());
+    assert_eq!(expected, [None, Some("x y".to_string())]);
+    assert_eq!(input, "1\n2\n");
+    assert_eq!(parse_sample("---\n\n").unwrap().2, "\n");
+    assert!(parse_sample("part1: 1\n1 2\n").is_err());
+    assert!(parse_sample("part3: 1\n---\n").is_err());
+}
+
+#[test]
+fn samples_test() {
+    let year = aoc::days::find_year(2024).unwrap();
+    let samples = load_samples(&samples_dir(year.number), &(1..=25).collect::<Vec<_>>()).unwrap();
+    let jobs: Vec<_> = samples
+        .iter()
+        .map(|sample| {
+            let day = year.find(sample.day_num).unwrap();
+            let (variant, solver) = day.variant(None).unwrap();
+            sample.job(variant, solver)
+        })
+        .collect();
+    let results = crate::runner::run_days(&jobs, 1, None);
+    check_samples(&samples, results, None).unwrap();
+}
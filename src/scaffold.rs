@@ -0,0 +1,234 @@
+use anyhow::{Context, bail, ensure};
+use std::fs;
+use std::path::Path;
+
+const YEARS_REGISTRY_FILE: &str = "src/days.rs";
+const TEMPLATE_FILE: &str = "src/day_template.rs";
+
+// Creates the files for a new day from the day template, and declares and registers its module on
+// its year's `days!` registry, so it can be run right away. A new year gets a registry of its own,
+// which is registered on the `years!` registry. Must be run from the project root.
+pub fn new_day(year: usize, day_num: usize, title: &str, input_dir: &str) -> aoc::Result<()> {
+    ensure!((1..=25).contains(&day_num), "day number out of range");
+    ensure!(!title.contains('"'), "day title cannot contain quotes");
+    let registry_file = format!("src/days/y{year}.rs");
+    let module = format!("day_{day_num:02}_{}", module_name(title));
+    let module_file = format!("src/days/y{year}/{module}.rs");
+    ensure!(
+        !Path::new(&module_file).exists(),
+        "{module_file} already exists"
+    );
+
+    let (registry_src, years_registry_src) = if Path::new(&registry_file).exists() {
+        (read_file(&registry_file)?, None)
+    } else {
+        let years_registry_src = read_file(YEARS_REGISTRY_FILE)?;
+        let registry_src = format!("//! The solutions of Advent of Code {year}.\n\ndays! {{\n}}\n");
+        (
+            registry_src,
+            Some(register_year(&years_registry_src, year)?),
+        )
+    };
+    let registry_src = register_day(&registry_src, day_num, title, &module)?;
+    let template = read_file(TEMPLATE_FILE)?;
+
+    write_file(&module_file, &template)?;
+    println!("Created {module_file}");
+    write_file(&registry_file, &registry_src)?;
+    println!("Registered day {day_num} in {registry_file}");
+    if let Some(years_registry_src) = years_registry_src {
+        write_file(YEARS_REGISTRY_FILE, &years_registry_src)?;
+        println!("Registered year {year} in {YEARS_REGISTRY_FILE}");
+    }
+    let input_file = crate::default_input_path(input_dir, year, day_num);
+    if !Path::new(&input_file).exists() {
+        write_file(&input_file, "")?;
+        println!("Created empty {input_file}");
+    }
+    Ok(())
+}
+
+fn read_file(path: &str) -> aoc::Result<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("error reading {path} (is this the project root?)"))
+}
+
+// Writes a file, creating its directory if needed.
+fn write_file(path: &str, contents: &str) -> aoc::Result<()> {
+    let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(path, contents))
+        .with_context(|| format!("error writing {path}"))
+}
+
+// Converts a title like "Red-Nosed Reports" into a module name like "red_nosed_reports".
+fn module_name(title: &str) -> String {
+    let words = title.split(|ch: char| !ch.is_ascii_alphanumeric());
+    let words: Vec<_> = words.filter(|w| !w.is_empty()).collect();
+    words.join("_").to_ascii_lowercase()
+}
+
+// Declares the module of the new day and adds a line for it to the `days!` registry in the given
+// source, keeping the days sorted.
+fn register_day(src: &str, day_num: usize, title: &str, module: &str) -> aoc::Result<String> {
+    let new_line = format!("    {day_num} \"{title}\": {module};");
+    register(src, "days!", day_num, &new_line)
+        .and_then(|src| declare_module(&src, "days!", module))
+        .with_context(|| format!("cannot register day {day_num}"))
+}
+
+// Declares the module of a new year and adds a line for it to the `years!` registry in the given
+// source, keeping the years sorted.
+fn register_year(src: &str, year: usize) -> aoc::Result<String> {
+    let new_line = format!("    {year}: y{year};");
+    register(src, "years!", year, &new_line)
+        .and_then(|src| declare_module(&src, "years!", &format!("y{year}")))
+        .with_context(|| format!("cannot register year {year}"))
+}
+
+// Adds a `pub mod` item for the module among the others, keeping them sorted, or before the given
+// registry if there are none yet.
+fn declare_module(src: &str, registry: &str, module: &str) -> aoc::Result<String> {
+    // The start and end of each `pub mod` line, and the module it declares.
+    let mut declarations = vec![];
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let declared = line.trim_end().strip_prefix("pub mod ");
+        if let Some(declared) = declared.and_then(|d| d.strip_suffix(';')) {
+            declarations.push((offset, offset + line.len(), declared));
+        }
+        offset += line.len();
+    }
+    ensure!(
+        declarations
+            .iter()
+            .all(|&(_, _, declared)| declared != module),
+        "module {module} already declared"
+    );
+
+    let new_item = format!("pub mod {module};\n");
+    let (pos, new_item) = match declarations.iter().rfind(|&&(_, _, d)| d < module) {
+        Some(&(_, end, _)) => (end, new_item),
+        None => match declarations.first() {
+            Some(&(start, _, _)) => (start, new_item),
+            None => {
+                let registry_start = src
+                    .find(&format!("{registry} {{\n"))
+                    .with_context(|| format!("{registry} registry not found"))?;
+                (registry_start, new_item + "\n")
+            }
+        },
+    };
+    Ok(format!("{}{new_item}{}", &src[..pos], &src[pos..]))
+}
+
+// Inserts a line into the block of the given registry macro, keeping its lines sorted by the number
+// they start with.
+fn register(src: &str, registry: &str, num: usize, new_line: &str) -> aoc::Result<String> {
+    let block_start = format!("{registry} {{\n");
+    let start = src
+        .find(&block_start)
+        .with_context(|| format!("{registry} registry not found"))?
+        + block_start.len();
+    let len = src[start..]
+        .find('}')
+        .with_context(|| format!("{registry} registry end not found"))?;
+    let mut lines: Vec<_> = src[start..start + len].lines().collect();
+    let registered_num = |line: &str| {
+        let mut digits = line.trim_start().split(|ch: char| !ch.is_ascii_digit());
+        digits.next()?.parse::<usize>().ok()
+    };
+
+    let pos = lines.partition_point(|&l| registered_num(l).is_some_and(|n| n < num));
+    if lines.get(pos).and_then(|&l| registered_num(l)) == Some(num) {
+        bail!("already registered");
+    }
+    lines.insert(pos, new_line);
+
+    Ok(format!(
+        "{}{}\n{}",
+        &src[..start],
+        lines.join("\n"),
+        &src[start + len..]
+    ))
+}
+
+#[test]
+fn module_name_test() {
+    assert_eq!(module_name("Historian Hysteria"), "historian_hysteria");
+    assert_eq!(module_name("Red-Nosed Reports"), "red_nosed_reports");
+    assert_eq!(module_name("RAM Run"), "ram_run");
+    assert_eq!(module_name("  It's 2025! "), "it_s_2025");
+}
+
+#[test]
+fn register_day_test() {
+    let src = "//! Docs.
+
+pub mod day_01_a;
+pub mod day_03_c;
+pub mod day_03_c_x;
+
+days! {
+    1 \"A\": day_01_a;
+    3 \"C\": day_03_c, x = day_03_c_x;
+}
+";
+    assert_eq!(
+        register_day(src, 2, "B", "day_02_b").unwrap(),
+        "//! Docs.
+
+pub mod day_01_a;
+pub mod day_02_b;
+pub mod day_03_c;
+pub mod day_03_c_x;
+
+days! {
+    1 \"A\": day_01_a;
+    2 \"B\": day_02_b;
+    3 \"C\": day_03_c, x = day_03_c_x;
+}
+"
+    );
+    assert_eq!(
+        register_day(src, 4, "D", "day_04_d").unwrap(),
+        "//! Docs.
+
+pub mod day_01_a;
+pub mod day_03_c;
+pub mod day_03_c_x;
+pub mod day_04_d;
+
+days! {
+    1 \"A\": day_01_a;
+    3 \"C\": day_03_c, x = day_03_c_x;
+    4 \"D\": day_04_d;
+}
+"
+    );
+    assert!(register_day(src, 3, "C", "day_03_c").is_err());
+    assert!(register_day(src, 5, "C", "day_03_c").is_err());
+    assert!(register_day("", 3, "C", "day_03_c").is_err());
+}
+
+#[test]
+fn register_year_test() {
+    let src = "mod foo;\npub mod y2024;\n\nyears! {\n    2024: y2024;\n}\n";
+    assert_eq!(
+        register_year(src, 2025).unwrap(),
+        "mod foo;\npub mod y2024;\npub mod y2025;\n\n\
+        years! {\n    2024: y2024;\n    2025: y2025;\n}\n"
+    );
+    assert_eq!(
+        register_year(src, 2023).unwrap(),
+        "mod foo;\npub mod y2023;\npub mod y2024;\n\n\
+        years! {\n    2023: y2023;\n    2024: y2024;\n}\n"
+    );
+    assert!(register_year(src, 2024).is_err());
+
+    // New years start with an empty days! registry.
+    assert_eq!(
+        register_day("//! Docs.\n\ndays! {\n}\n", 1, "A", "day_01_a").unwrap(),
+        "//! Docs.\n\npub mod day_01_a;\n\ndays! {\n    1 \"A\": day_01_a;\n}\n"
+    );
+}
@@ -0,0 +1,326 @@
+use crate::output::{self, json_string};
+use crate::runner::{self, Job};
+use anyhow::{Context, anyhow, bail, ensure};
+use aoc::days;
+use itertools::Itertools;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 2024;
+
+// Puzzle inputs are a few KiB; anything this big is surely a mistake.
+const MAX_BODY_LEN: usize = 10 << 20;
+
+// How long a client can take to send its request or read the response before it's dropped.
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Connections handled at once; more are turned away with a 503 so that a flood of requests can't
+// start an unbounded number of threads.
+const MAX_CONNECTIONS: usize = 16;
+
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub timeout: Option<Duration>,
+    pub strict_input: bool,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+// A response status and its JSON body.
+type Response = (u16, String);
+
+// Serves an HTTP API for running the days on localhost. `POST /days/{n}` runs day n on the request
+// body, with the optional `part`, `variant` and `year` query parameters working like the --part,
+// --variant and --year options, and responds with its answers and run time in JSON.
+//
+// Days run on threads of the server process, so this is only meant for trusted local clients: a day
+// that times out keeps running in the background until it's done, and one that overflows its stack
+// aborts the whole server.
+pub fn serve(port: u16, settings: Settings) -> aoc::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("error listening on port {port}"))?;
+    println!("Listening on http://127.0.0.1:{port}");
+    serve_on(listener, settings)
+}
+
+fn serve_on(listener: TcpListener, settings: Settings) -> aoc::Result<()> {
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        // A failed accept (e.g. from running out of file descriptors) only loses that connection.
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("error accepting connection: {err}");
+                continue;
+            }
+        };
+        // These only fail with a zero timeout.
+        _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+        _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+        if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            let response = error_response(503, "too many connections, try again later");
+            write_response(&mut stream, response);
+            continue;
+        }
+        let connections = Arc::clone(&connections);
+        thread::spawn(move || {
+            handle_connection(stream, settings);
+            connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, settings: Settings) {
+    let response = match read_request(&stream) {
+        Ok(request) => respond(&request, settings),
+        Err(err) => error_response(400, format!("{err:#}")),
+    };
+    write_response(&mut stream, response);
+}
+
+fn write_response(stream: &mut TcpStream, (status, body): Response) {
+    let response = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+        Connection: close\r\n\r\n{body}",
+        reason_phrase(status),
+        body.len()
+    );
+    // There's nothing to do if the client is gone.
+    _ = stream.write_all(response.as_bytes());
+}
+
+fn read_request(stream: &TcpStream) -> aoc::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let Some((method, target, _version)) = request_line.split_whitespace().collect_tuple() else {
+        bail!("invalid request line");
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| -> aoc::Result<_> {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            let decode = |s| percent_decode(s).with_context(|| format!("invalid query '{param}'"));
+            Ok((decode(name)?, decode(value)?))
+        })
+        .try_collect()?;
+
+    let mut content_len = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_len = value.trim().parse().context("invalid Content-Length")?;
+        }
+    }
+    ensure!(content_len <= MAX_BODY_LEN, "request body is too large");
+    let mut body = vec![0; content_len];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).context("request body is not valid UTF-8")?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+// Decodes the %XX escapes and + signs (for spaces) of a query parameter.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'%' => {
+                let hex = std::str::from_utf8(rest.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &rest[2..];
+            }
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn respond(request: &Request, settings: Settings) -> Response {
+    let Some(day_num) = request.path.strip_prefix("/days/") else {
+        return error_response(404, "not found");
+    };
+    if request.method != "POST" {
+        return error_response(405, "days must be run with POST");
+    }
+    let (mut part, mut variant, mut year) = (None, None, days::latest_year());
+    for (name, value) in &request.query {
+        match name.as_str() {
+            "year" => match value.parse().ok().and_then(days::find_year) {
+                Some(y) => year = y,
+                None => return error_response(404, format!("no solutions for year '{value}'")),
+            },
+            "part" if value == "1" || value == "2" => part = value.parse().ok(),
+            "part" => return error_response(400, "part number must be 1 or 2"),
+            "variant" => variant = Some(value.as_str()),
+            _ => return error_response(400, format!("unknown parameter '{name}'")),
+        }
+    }
+    let Some(day) = day_num.parse().ok().and_then(|n| year.find(n)) else {
+        return error_response(404, "day not found");
+    };
+    let (variant, solver) = match day.variant(variant) {
+        Ok(variant) => variant,
+        Err(err) => return error_response(400, format!("{err:#}")),
+    };
+
+    let mut job = Job::new(day.number, variant, solver, "request".to_string());
+    job.input = Some(request.body.clone());
+    job.strict_input = settings.strict_input;
+    let mut results = runner::run_days(&[job], 1, settings.timeout);
+    let result = results
+        .next()
+        .unwrap_or_else(|| Err(anyhow!("worker thread disconnected")));
+
+    let status = if result.is_ok() { 200 } else { 422 };
+    let [part_1, part_2, elapsed_ms, error] = output::record_fields(&result, part);
+    let [part_1, part_2, error] = [part_1, part_2, error].map(|f| match f {
+        Some(s) => json_string(&s),
+        None => "null".to_string(),
+    });
+    let elapsed_ms = elapsed_ms.unwrap_or("null".to_string());
+    let body = format!(
+        "{{\"year\": {}, \"day\": {}, \"variant\": {}, \"part1\": {part_1}, \"part2\": {part_2}, \
+        \"elapsed_ms\": {elapsed_ms}, \"error\": {error}}}",
+        year.number,
+        day.number,
+        json_string(variant)
+    );
+    (status, body)
+}
+
+fn error_response(status: u16, message: impl AsRef<str>) -> Response {
+    let body = format!("{{\"error\": {}}}", json_string(message.as_ref()));
+    (status, body)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        422 => "Unprocessable Content",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[test]
+fn serve_test() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let settings = Settings {
+        timeout: Some(Duration::from_secs(10)),
+        strict_input: false,
+    };
+    thread::spawn(move || serve_on(listener, settings));
+
+    let request = |method: &str, target: &str, body: &str| {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let len = body.len();
+        let request = format!("{method} {target} HTTP/1.1\r\nContent-Length: {len}\r\n\r\n{body}");
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status_line = head.lines().next().unwrap().to_string();
+        (status_line, body.to_string())
+    };
+
+    let sample = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    let (status, body) = request("POST", "/days/1", sample);
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.starts_with(
+        r#"{"year": 2024, "day": 1, "variant": "default", "part1": "11", "part2": "31", "elapsed_ms": "#
+    ));
+    assert!(body.ends_with(r#", "error": null}"#));
+
+    let (status, body) = request("POST", "/days/1?part=2", sample);
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains(r#""part1": null, "part2": "31""#));
+
+    let (status, body) = request("POST", "/days/1", "3   x\n");
+    assert_eq!(status, "HTTP/1.1 422 Unprocessable Content");
+    assert!(body.contains(r#""part1": null"#));
+    assert!(body.contains(r#""error": "request:1:1: expected two numbers"#));
+
+    let (status, body) = request("POST", "/days/1?variant=nope", sample);
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert_eq!(
+        body,
+        r#"{"error": "day 1 has no 'nope' variant (available: default)"}"#
+    );
+
+    let (status, _) = request("POST", "/days/1?part=3", sample);
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    let (status, _) = request("GET", "/days/1", "");
+    assert_eq!(status, "HTTP/1.1 405 Method Not Allowed");
+    let (status, _) = request("POST", "/days/1?year=2024&part=1", sample);
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    let (status, _) = request("POST", "/days/1?year=2015", sample);
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    let (status, _) = request("POST", "/days/26", sample);
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    let (status, _) = request("POST", "/", sample);
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+
+    let maze = "#####\n#..E#\n#S..#\n#####\n";
+    let (status, body) = request("POST", "/days/16?variant=custom%5Fdijkstra", maze);
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.contains(r#""variant": "custom_dijkstra""#));
+    let (status, body) = request("POST", "/days/1?variant=no+pe%21", sample);
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert!(body.contains("no 'no pe!' variant"));
+    let (status, body) = request("POST", "/days/1?part=%2", sample);
+    assert_eq!(status, "HTTP/1.1 400 Bad Request");
+    assert_eq!(body, r#"{"error": "invalid query 'part=%2'"}"#);
+}
+
+#[test]
+fn max_connections_test() {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let settings = Settings {
+        timeout: None,
+        strict_input: false,
+    };
+    thread::spawn(move || serve_on(listener, settings));
+
+    // Connections that haven't sent their request yet keep their slots.
+    let _idle = (0..MAX_CONNECTIONS)
+        .map(|_| TcpStream::connect(addr).unwrap())
+        .collect_vec();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+}
@@ -0,0 +1,94 @@
+use crate::output::json_string;
+use crate::runner::{self, Job};
+use anyhow::{Context, ensure};
+use std::fs;
+use std::time::{Duration, Instant};
+
+// The width of the span names column, including their indentation.
+const NAME_WIDTH: usize = 24;
+
+// Runs each day once, one after the other, and prints a breakdown of its run time into the spans
+// recorded by `aoc::span()`. If `chrome_trace_file` is given, the spans are also written to it in the
+// Chrome trace event format, which can be opened with chrome://tracing or https://ui.perfetto.dev.
+pub fn run_traced(jobs: &[Job], chrome_trace_file: Option<&str>) -> aoc::Result<()> {
+    let trace_start = Instant::now();
+    let mut events = vec![];
+    let mut fail_count = 0;
+    for job in jobs {
+        let day = format!("Day {}", job.day_num);
+        let result = runner::run_on_day_thread(|| {
+            let input = job.read_input()?;
+            let day_start = trace_start.elapsed();
+            let (answer, spans) = aoc::trace(|| (job.solver)(&input, &job.params));
+            let elapsed = trace_start.elapsed() - day_start;
+            answer.map_err(|err| aoc::locate_error(err, &input, job.input_name()))?;
+            Ok((day_start, elapsed, spans))
+        });
+        let (day_start, elapsed, spans) = match result {
+            Ok(trace) => trace,
+            Err(err) => {
+                fail_count += 1;
+                println!("{day:<NAME_WIDTH$} FAILED ({err:#})");
+                continue;
+            }
+        };
+
+        println!("{day:<NAME_WIDTH$} {:>10}", format!("{elapsed:.2?}"));
+        for span in &spans {
+            let indent = "  ".repeat(span.depth + 1);
+            let name = format!("{indent}{}", span.name);
+            // Days can be too quick to measure.
+            let pct = match elapsed.is_zero() {
+                true => 0.0,
+                false => span.elapsed.as_secs_f64() / elapsed.as_secs_f64() * 100.0,
+            };
+            println!(
+                "{name:<NAME_WIDTH$} {:>10} {pct:>4.0}%",
+                format!("{:.2?}", span.elapsed)
+            );
+        }
+
+        events.push(trace_event(&day, job.day_num, day_start, elapsed));
+        for span in spans {
+            let start = day_start + span.start;
+            events.push(trace_event(span.name, job.day_num, start, span.elapsed));
+        }
+    }
+
+    if let Some(path) = chrome_trace_file {
+        let contents = format!("{{\"traceEvents\": [\n  {}\n]}}\n", events.join(",\n  "));
+        fs::write(path, contents).with_context(|| format!("error writing {path}"))?;
+        println!("\nWrote Chrome trace to {path}");
+    }
+    ensure!(
+        fail_count == 0,
+        "{fail_count} of {} days failed",
+        jobs.len()
+    );
+    Ok(())
+}
+
+// A "complete" event of the Chrome trace event format. Each day gets a thread of its own, so they are
+// shown on separate tracks.
+fn trace_event(name: &str, day_num: usize, start: Duration, elapsed: Duration) -> String {
+    let [ts, dur] = [start, elapsed].map(|d| format!("{:.3}", d.as_secs_f64() * 1e6));
+    let name = json_string(name);
+    format!(
+        "{{\"name\": {name}, \"ph\": \"X\", \"ts\": {ts}, \"dur\": {dur}, \"pid\": 1, \
+        \"tid\": {day_num}}}"
+    )
+}
+
+#[test]
+fn trace_event_test() {
+    let event = trace_event(
+        "part 2",
+        6,
+        Duration::from_micros(1500),
+        Duration::from_nanos(2500),
+    );
+    assert_eq!(
+        event,
+        r#"{"name": "part 2", "ph": "X", "ts": 1500.000, "dur": 2.500, "pid": 1, "tid": 6}"#
+    );
+}